@@ -130,13 +130,13 @@ async fn can_build_message_proof() {
     let commit_block_header = PartialBlockHeader {
         application: ApplicationHeader {
             da_height: 0u64.into(),
-            generated: Default::default(),
+            ..Default::default()
         },
         consensus: ConsensusHeader {
             prev_root: Bytes32::zeroed(),
             height: commit_block_height,
             time: Tai64::UNIX_EPOCH,
-            generated: Default::default(),
+            ..Default::default()
         },
     }
     .generate(&[], &[]);
@@ -144,13 +144,13 @@ async fn can_build_message_proof() {
     let message_block_header = PartialBlockHeader {
         application: ApplicationHeader {
             da_height: 0u64.into(),
-            generated: Default::default(),
+            ..Default::default()
         },
         consensus: ConsensusHeader {
             prev_root: Bytes32::zeroed(),
             height: message_block_height,
             time: Tai64::UNIX_EPOCH,
-            generated: Default::default(),
+            ..Default::default()
         },
     }
     .generate(&[], &message_ids);
@@ -30,12 +30,7 @@ use fuel_core_types::{
             Consensus,
             Genesis,
         },
-        header::{
-            ApplicationHeader,
-            ConsensusHeader,
-            PartialBlockHeader,
-        },
-        primitives::Empty,
+        header::PartialBlockHeaderBuilder,
         SealedBlock,
     },
     entities::{
@@ -99,27 +94,23 @@ fn import_genesis_block(
     };
 
     let block = Block::new(
-        PartialBlockHeader {
-            application: ApplicationHeader::<Empty> {
-                // TODO: Set `da_height` based on the chain config.
-                da_height: Default::default(),
-                generated: Empty,
-            },
-            consensus: ConsensusHeader::<Empty> {
-                // The genesis is a first block, so previous root is zero.
-                prev_root: Bytes32::zeroed(),
-                // The initial height is defined by the `ChainConfig`.
-                // If it is `None` then it will be zero.
-                height: config
+        PartialBlockHeaderBuilder::new()
+            // TODO: Set `da_height` based on the chain config.
+            .da_height(Default::default())
+            // The genesis is a first block, so previous root is zero.
+            .prev_root(Bytes32::zeroed())
+            // The initial height is defined by the `ChainConfig`.
+            // If it is `None` then it will be zero.
+            .height(
+                config
                     .chain_conf
                     .initial_state
                     .as_ref()
                     .map(|config| config.height.unwrap_or_else(|| 0u32.into()))
                     .unwrap_or_else(|| 0u32.into()),
-                time: fuel_core_types::tai64::Tai64::UNIX_EPOCH,
-                generated: Empty,
-            },
-        },
+            )
+            .time(fuel_core_types::tai64::Tai64::UNIX_EPOCH)
+            .build(),
         // Genesis block doesn't have any transaction.
         vec![],
         &[],
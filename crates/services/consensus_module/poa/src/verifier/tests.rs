@@ -30,6 +30,18 @@ fn app_hash(da_height: u64) -> Bytes32 {
 }
 
 fn correct() -> Input {
+    // The update sets the `time_nanos` of the `nanos` feature of the `fuel-core-types`.
+    #[allow(clippy::needless_update)]
+    let ch = ConsensusHeader {
+        prev_root: [2u8; 32].into(),
+        height: 2u32.into(),
+        time: Tai64(2),
+        generated: GeneratedConsensusFields {
+            application_hash: app_hash(2),
+        },
+        ..Default::default()
+    };
+
     Input {
         c: Config {
             enabled_manual_blocks: false,
@@ -37,14 +49,7 @@ fn correct() -> Input {
         block_header_merkle_root: [2u8; 32],
         prev_header_time: Tai64(2),
         prev_header_da_height: 2,
-        ch: ConsensusHeader {
-            prev_root: [2u8; 32].into(),
-            height: 2u32.into(),
-            time: Tai64(2),
-            generated: GeneratedConsensusFields {
-                application_hash: app_hash(2),
-            },
-        },
+        ch,
         ah: ApplicationHeader {
             da_height: 2u64.into(),
             ..Default::default()
@@ -10,9 +10,8 @@ use fuel_core_storage::transactional::StorageTransaction;
 use fuel_core_types::{
     blockchain::{
         header::{
-            ApplicationHeader,
-            ConsensusHeader,
             PartialBlockHeader,
+            PartialBlockHeaderBuilder,
         },
         primitives::DaBlockHeight,
     },
@@ -206,18 +205,12 @@ where
     ) -> anyhow::Result<PartialBlockHeader> {
         let previous_block_info = self.previous_block_info(height)?;
 
-        Ok(PartialBlockHeader {
-            application: ApplicationHeader {
-                da_height: previous_block_info.da_height,
-                generated: Default::default(),
-            },
-            consensus: ConsensusHeader {
-                prev_root: previous_block_info.prev_root,
-                height,
-                time: block_time,
-                generated: Default::default(),
-            },
-        })
+        Ok(PartialBlockHeaderBuilder::new()
+            .da_height(previous_block_info.da_height)
+            .prev_root(previous_block_info.prev_root)
+            .height(height)
+            .time(block_time)
+            .build())
     }
 
     fn previous_block_info(
@@ -3,15 +3,10 @@
 use super::{
     consensus::ConsensusType,
     header::{
-        ApplicationHeader,
         BlockHeader,
-        ConsensusHeader,
         PartialBlockHeader,
     },
-    primitives::{
-        BlockId,
-        Empty,
-    },
+    primitives::BlockId,
 };
 use crate::{
     fuel_tx::{
@@ -181,33 +176,11 @@ impl PartialFuelBlock {
 impl From<Block> for PartialFuelBlock {
     fn from(block: Block) -> Self {
         let Block {
-            header:
-                BlockHeader {
-                    application: ApplicationHeader { da_height, .. },
-                    consensus:
-                        ConsensusHeader {
-                            prev_root,
-                            height,
-                            time,
-                            ..
-                        },
-                    ..
-                },
+            header,
             transactions,
         } = block;
         Self {
-            header: PartialBlockHeader {
-                application: ApplicationHeader {
-                    da_height,
-                    generated: Empty {},
-                },
-                consensus: ConsensusHeader {
-                    prev_root,
-                    height,
-                    time,
-                    generated: Empty {},
-                },
-            },
+            header: header.into_partial(),
             transactions,
         }
     }
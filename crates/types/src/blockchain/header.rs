@@ -1,5 +1,24 @@
 //! Block header types
+//!
+//! # Consensus-breaking features
+//!
+//! The `multi-da` and `nanos` features are not additive. The `multi-da` adds
+//! the `da_chain` to the [`ApplicationHeader`] and to its hash, and the `nanos`
+//! adds the `time_nanos` to the [`ConsensusHeader`] and to the block id.
+//! Both change the postcard layout, the canonical and compact encodings, and
+//! the ids of all blocks. Cargo unifies the features, so enabling either of
+//! them in any crate of the workspace switches every node binary built from it
+//! to the incompatible format. Nodes built with and without them can't share
+//! the database or talk over the P2P. The features are intended only for
+//! networks that are started with them from the genesis.
+//!
+//! The `serialize-metadata` feature is format-breaking for the same reason: it
+//! appends the cached id to the serde layout of the [`BlockHeader`], so the
+//! postcard of the database and the P2P can't decode the headers encoded
+//! without it, and vice versa.
 
+#[cfg(feature = "multi-da")]
+use super::primitives::DaChainId;
 use super::{
     consensus::ConsensusType,
     primitives::{
@@ -9,6 +28,11 @@ use super::{
     },
 };
 use crate::{
+    entities::message::MerkleProof,
+    fuel_crypto::{
+        PublicKey,
+        Signature,
+    },
     fuel_merkle,
     fuel_tx::Transaction,
     fuel_types::{
@@ -18,12 +42,57 @@ use crate::{
         MessageId,
     },
 };
+use std::borrow::Cow;
 use tai64::Tai64;
+#[cfg(feature = "nanos")]
+use tai64::Tai64N;
+
+pub use error::{
+    CanonicalBytesError,
+    ChainValidationError,
+    DaHeightError,
+    GenerateError,
+    HeaderValidationError,
+    TimeError,
+};
+#[cfg(feature = "graphql")]
+pub use graphql::{
+    GraphQlBlockHeader,
+    GraphQlHeaderError,
+};
+#[cfg(feature = "protobuf")]
+pub use protobuf::{
+    HeaderProto,
+    ProtoHeaderError,
+};
+
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+pub mod error;
+#[cfg(feature = "graphql")]
+mod graphql;
+#[cfg(feature = "protobuf")]
+mod protobuf;
+#[cfg(feature = "serialize-metadata")]
+mod serde_metadata;
+#[cfg(feature = "serde")]
+pub mod serde_rfc3339;
+#[cfg(feature = "serde")]
+mod serde_version;
+#[cfg(any(test, feature = "test-helpers"))]
+pub mod test_helpers;
+#[cfg(test)]
+mod tests;
 
 /// A fuel block header that has all the fields generated because it
 /// has been executed.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(
+    feature = "serialize-metadata",
+    serde(try_from = "serde_metadata::BlockHeaderWithId")
+)]
 pub struct BlockHeader {
     /// The application header.
     pub application: ApplicationHeader<GeneratedApplicationFields>,
@@ -31,10 +100,59 @@ pub struct BlockHeader {
     pub consensus: ConsensusHeader<GeneratedConsensusFields>,
     /// The header metadata calculated during creation.
     /// The field is private to enforce the use of the [`PartialBlockHeader::generate`] method.
-    #[cfg_attr(feature = "serde", serde(skip))]
+    #[cfg_attr(
+        all(feature = "serde", not(feature = "serialize-metadata")),
+        serde(skip)
+    )]
+    #[cfg_attr(
+        feature = "serialize-metadata",
+        serde(serialize_with = "serde_metadata::serialize")
+    )]
     metadata: Option<BlockHeaderMetadata>,
 }
 
+impl PartialEq for BlockHeader {
+    fn eq(&self, other: &Self) -> bool {
+        // The `metadata` is only a cache of values derived from the header,
+        // so it doesn't participate in the comparison. Because of that, equal
+        // headers always produce the same `hash()`.
+        self.application == other.application && self.consensus == other.consensus
+    }
+}
+
+impl Eq for BlockHeader {}
+
+impl core::hash::Hash for BlockHeader {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        // Like the `Eq`, it ignores the `metadata`, so a stale cached id doesn't
+        // change the hash of equal headers.
+        core::hash::Hash::hash(&self.application, state);
+        core::hash::Hash::hash(&self.consensus, state);
+    }
+}
+
+/// Headers are ordered by the height, then by the time, and then by the rest of
+/// the fields, so headers of different producers at the same height are ordered
+/// deterministically. It is not the order of the chain: it doesn't verify that
+/// headers are linked.
+///
+/// Like the `Eq`, the order ignores the cached metadata.
+impl PartialOrd for BlockHeader {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BlockHeader {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.height()
+            .cmp(other.height())
+            .then_with(|| self.time().cmp(&other.time()))
+            .then_with(|| self.application.cmp(&other.application))
+            .then_with(|| self.consensus.cmp(&other.consensus))
+    }
+}
+
 #[derive(Clone, Debug)]
 #[cfg_attr(any(test, feature = "test-helpers"), derive(Default))]
 /// A partially complete fuel block header that doesn't not
@@ -46,12 +164,34 @@ pub struct PartialBlockHeader {
     pub consensus: ConsensusHeader<Empty>,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// The builder of the [`PartialBlockHeader`].
+/// Fields that are not set use the values of the genesis block.
+#[derive(Clone, Debug)]
+pub struct PartialBlockHeaderBuilder {
+    header: PartialBlockHeader,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[cfg_attr(any(test, feature = "test-helpers"), derive(Default))]
 /// The fuel block application header.
 /// Contains everything except consensus related data.
 pub struct ApplicationHeader<Generated> {
+    /// The version of the header format.
+    ///
+    /// The [`HeaderVersion::V0`] is omitted from the serde layout, so the headers
+    /// stored before the versioning are decoded. Binary formats can't serialize
+    /// other versions, see the `serde_version` module.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "serde_version::is_v0",
+            with = "serde_version"
+        )
+    )]
+    pub version: HeaderVersion,
     /// The layer 1 height of messages and events to include since the last layer 1 block number.
     /// This is not meant to represent the layer 1 block this was committed to. Validators will need
     /// to have some rules in place to ensure the block number was chosen in a reasonable way. For
@@ -59,12 +199,33 @@ pub struct ApplicationHeader<Generated> {
     /// layer 1 chain. They should also verify that the block number isn't too stale and is increasing.
     /// Some similar concerns are noted in this issue: https://github.com/FuelLabs/fuel-specs/issues/220
     pub da_height: DaBlockHeight,
+    /// The layer 1 chain of the `da_height`.
+    ///
+    /// The field is a part of the hash and the encodings, so the `multi-da`
+    /// feature is consensus-breaking, see the [module](self) documentation.
+    #[cfg(feature = "multi-da")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub da_chain: DaChainId,
     /// Generated application fields.
     pub generated: Generated,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+/// The version of the header format. It defines which fields
+/// are a part of the preimage of the [`ApplicationHeader::hash`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[repr(u8)]
+pub enum HeaderVersion {
+    /// The initial format. The version is not a part of the hash.
+    #[default]
+    V0 = 0,
+    /// The version byte is the first input of the hash.
+    V1 = 1,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[cfg_attr(any(test, feature = "test-helpers"), derive(Default))]
 /// Concrete generated application header fields.
 /// These are generated once the full block has been run.
@@ -79,8 +240,58 @@ pub struct GeneratedApplicationFields {
     pub message_receipt_root: Bytes32,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// The fields that differ between two [`GeneratedApplicationFields`],
+/// see [`GeneratedApplicationFields::fields_diff`].
+pub struct ApplicationFieldsDiff {
+    /// The `transactions_count` differs.
+    pub transactions_count: bool,
+    /// The `message_receipt_count` differs.
+    pub message_receipt_count: bool,
+    /// The `transactions_root` differs.
+    pub transactions_root: bool,
+    /// The `message_receipt_root` differs.
+    pub message_receipt_root: bool,
+}
+
+impl ApplicationFieldsDiff {
+    /// Returns the union of the fields changed in `self` and the `other`,
+    /// for example, to summarize changes across several re-executions.
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            transactions_count: self.transactions_count || other.transactions_count,
+            message_receipt_count: self.message_receipt_count
+                || other.message_receipt_count,
+            transactions_root: self.transactions_root || other.transactions_root,
+            message_receipt_root: self.message_receipt_root || other.message_receipt_root,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+/// The fields that differ between two [`BlockHeader`]s, see [`BlockHeader::diff`].
+pub struct HeaderDiff {
+    /// The `version` differs.
+    pub version: bool,
+    /// The `da_height` differs. With the `multi-da` feature,
+    /// it is also set if the `da_chain` differs.
+    pub da_height: bool,
+    /// The generated application fields that differ.
+    pub generated: ApplicationFieldsDiff,
+    /// The `prev_root` differs.
+    pub prev_root: bool,
+    /// The `height` differs.
+    pub height: bool,
+    /// The `time` differs. With the `nanos` feature,
+    /// it is also set if the `time_nanos` differs.
+    pub time: bool,
+    /// The `application_hash` differs.
+    pub application_hash: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 /// The fuel block consensus header.
 /// This contains fields related to consensus plus
 /// the hash of the [`ApplicationHeader`].
@@ -91,12 +302,20 @@ pub struct ConsensusHeader<Generated> {
     pub height: BlockHeight,
     /// The block producer time.
     pub time: Tai64,
+    /// The nanoseconds of the block producer time, less than `1_000_000_000`.
+    ///
+    /// The field is a part of the block id and the encodings, so the `nanos`
+    /// feature is consensus-breaking, see the [module](self) documentation.
+    #[cfg(feature = "nanos")]
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub time_nanos: u32,
     /// generated consensus fields.
     pub generated: Generated,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[cfg_attr(any(test, feature = "test-helpers"), derive(Default))]
 /// Concrete generated consensus header fields.
 /// These are generated once the full block has been run.
@@ -107,12 +326,387 @@ pub struct GeneratedConsensusFields {
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[cfg_attr(any(test, feature = "test-helpers"), derive(Default))]
 /// Extra data that is not actually part of the header.
 pub struct BlockHeaderMetadata {
     /// Hash of the header.
     id: BlockId,
 }
 
+/// The minimal information required to identify and order a [`BlockHeader`],
+/// for example, during the gossip between peers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct HeaderSummary {
+    /// The id of the block.
+    pub id: BlockId,
+    /// The height of the block.
+    pub height: BlockHeight,
+    /// The merkle root of all previous block ids.
+    pub prev_root: Bytes32,
+}
+
+/// The [`BlockHeader`] with the signature of the block producer over its id.
+///
+/// The signature is not a part of the hash of the header.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SignedBlockHeader {
+    /// The signed header.
+    pub header: BlockHeader,
+    /// The signature over the id of the header.
+    pub signature: Signature,
+}
+
+impl SignedBlockHeader {
+    /// Creates the signed header from the `header` and its `signature`.
+    pub fn new(header: BlockHeader, signature: Signature) -> Self {
+        Self { header, signature }
+    }
+
+    /// Returns `true` if the signature is made by the owner of the `public_key`
+    /// over the id of the header.
+    pub fn verify_signature(&self, public_key: &PublicKey) -> bool {
+        let id = self.header.id();
+        self.signature.verify(public_key, id.as_message()).is_ok()
+    }
+}
+
+/// The [`BlockHeader`] that passed the [`BlockHeader::validate`].
+///
+/// It can be obtained only via the [`BlockHeader::into_validated`], so functions
+/// accepting it don't need to validate the header again.
+///
+/// ```compile_fail
+/// use fuel_core_types::blockchain::header::{
+///     PartialBlockHeaderBuilder,
+///     ValidatedBlockHeader,
+/// };
+///
+/// let header = PartialBlockHeaderBuilder::new().build().generate(&[], &[]);
+/// let validated = ValidatedBlockHeader(header);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidatedBlockHeader(BlockHeader);
+
+impl ValidatedBlockHeader {
+    /// Returns the validated header.
+    pub fn into_inner(self) -> BlockHeader {
+        self.0
+    }
+}
+
+impl core::ops::Deref for ValidatedBlockHeader {
+    type Target = BlockHeader;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// The owned or borrowed [`BlockHeader`], for example, for caches that hold
+/// either of them.
+///
+/// The read accessors of the [`BlockHeader`] are available via [`Deref`](core::ops::Deref).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CowBlockHeader<'a>(Cow<'a, BlockHeader>);
+
+impl<'a> CowBlockHeader<'a> {
+    /// Returns `true` if the header is borrowed.
+    pub fn is_borrowed(&self) -> bool {
+        matches!(self.0, Cow::Borrowed(_))
+    }
+
+    /// Returns the owned header, cloning it if it is borrowed.
+    pub fn into_owned(self) -> BlockHeader {
+        self.0.into_owned()
+    }
+}
+
+impl<'a> core::ops::Deref for CowBlockHeader<'a> {
+    type Target = BlockHeader;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<BlockHeader> for CowBlockHeader<'_> {
+    fn from(header: BlockHeader) -> Self {
+        Self(Cow::Owned(header))
+    }
+}
+
+impl<'a> From<&'a BlockHeader> for CowBlockHeader<'a> {
+    fn from(header: &'a BlockHeader) -> Self {
+        Self(Cow::Borrowed(header))
+    }
+}
+
+/// The pool of the interned roots for the [`ArcBlockHeader`]s.
+///
+/// Each distinct root is stored once and shared by all headers created
+/// with the pool.
+#[derive(Clone, Debug, Default)]
+pub struct RootPool {
+    roots: std::collections::HashSet<std::sync::Arc<Bytes32>>,
+}
+
+impl RootPool {
+    /// Creates the empty pool.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the shared copy of the `root`, adding it to the pool if it is new.
+    pub fn intern(&mut self, root: &Bytes32) -> std::sync::Arc<Bytes32> {
+        if let Some(interned) = self.roots.get(root) {
+            return interned.clone()
+        }
+        let interned = std::sync::Arc::new(*root);
+        self.roots.insert(interned.clone());
+        interned
+    }
+
+    /// The number of distinct roots in the pool.
+    pub fn len(&self) -> usize {
+        self.roots.len()
+    }
+
+    /// Returns `true` if the pool has no roots.
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+}
+
+/// The [`BlockHeader`] that shares the `transactions_root`, the `message_receipt_root`,
+/// and the `application_hash` with other headers via the [`RootPool`], to hold
+/// many headers in memory.
+///
+/// Empty blocks have the same roots of the empty tree, and their `application_hash`
+/// is the same while the `da_height` doesn't change. On 64-bit targets without
+/// the optional features, the header takes 136 bytes instead of the 216 bytes
+/// of the [`BlockHeader`], so the chain of empty blocks takes about 37% less memory.
+/// The `prev_root` is unique for each block and is not shared. Each root that is
+/// not shared with other headers costs the extra 48 bytes of its allocation: the
+/// 16 bytes of the reference counters of the `Arc` and the 32 bytes of the root.
+///
+/// The id is calculated from the fields instead of taken from the cached metadata,
+/// so a stale cached id of the interned header is not preserved.
+#[derive(Clone, Debug)]
+pub struct ArcBlockHeader {
+    application: ApplicationHeader<Empty>,
+    transactions_count: u64,
+    message_receipt_count: u64,
+    transactions_root: std::sync::Arc<Bytes32>,
+    message_receipt_root: std::sync::Arc<Bytes32>,
+    consensus: ConsensusHeader<Empty>,
+    application_hash: std::sync::Arc<Bytes32>,
+    id: BlockId,
+}
+
+impl ArcBlockHeader {
+    /// Creates the header sharing the roots of the `header` via the `pool`.
+    ///
+    /// The id is calculated from the consensus header, like [`BlockHeader::recalculate_metadata`].
+    pub fn new(header: &BlockHeader, pool: &mut RootPool) -> Self {
+        let BlockHeader {
+            application,
+            consensus,
+            ..
+        } = header;
+        let generated = &application.generated;
+        Self {
+            application: ApplicationHeader {
+                version: application.version,
+                da_height: application.da_height,
+                #[cfg(feature = "multi-da")]
+                da_chain: application.da_chain,
+                generated: Empty,
+            },
+            transactions_count: generated.transactions_count,
+            message_receipt_count: generated.message_receipt_count,
+            transactions_root: pool.intern(&generated.transactions_root),
+            message_receipt_root: pool.intern(&generated.message_receipt_root),
+            consensus: ConsensusHeader {
+                prev_root: consensus.prev_root,
+                height: consensus.height,
+                time: consensus.time,
+                #[cfg(feature = "nanos")]
+                time_nanos: consensus.time_nanos,
+                generated: Empty,
+            },
+            application_hash: pool.intern(&consensus.generated.application_hash),
+            id: consensus.hash(),
+        }
+    }
+
+    /// The cached fuel header hash.
+    pub fn id(&self) -> BlockId {
+        self.id
+    }
+
+    /// Fuel block height.
+    pub fn height(&self) -> &BlockHeight {
+        &self.consensus.height
+    }
+
+    /// Returns the [`BlockHeader`] with the copied roots and the populated metadata.
+    pub fn to_header(&self) -> BlockHeader {
+        BlockHeader {
+            application: ApplicationHeader {
+                version: self.application.version,
+                da_height: self.application.da_height,
+                #[cfg(feature = "multi-da")]
+                da_chain: self.application.da_chain,
+                generated: GeneratedApplicationFields {
+                    transactions_count: self.transactions_count,
+                    message_receipt_count: self.message_receipt_count,
+                    transactions_root: *self.transactions_root,
+                    message_receipt_root: *self.message_receipt_root,
+                },
+            },
+            consensus: ConsensusHeader {
+                prev_root: self.consensus.prev_root,
+                height: self.consensus.height,
+                time: self.consensus.time,
+                #[cfg(feature = "nanos")]
+                time_nanos: self.consensus.time_nanos,
+                generated: GeneratedConsensusFields {
+                    application_hash: *self.application_hash,
+                },
+            },
+            metadata: Some(BlockHeaderMetadata { id: self.id }),
+        }
+    }
+}
+
+/// The flat representation of the [`BlockHeader`], for example,
+/// to store it as a database row.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct BlockHeaderRow {
+    /// The version of the application header.
+    pub version: HeaderVersion,
+    /// The layer 1 height of messages and events to include since the last layer 1 block number.
+    pub da_height: DaBlockHeight,
+    /// The layer 1 chain of the `da_height`.
+    #[cfg(feature = "multi-da")]
+    pub da_chain: DaChainId,
+    /// Number of transactions in this block.
+    pub transactions_count: u64,
+    /// Number of message receipts in this block.
+    pub message_receipt_count: u64,
+    /// Merkle root of transactions.
+    pub transactions_root: Bytes32,
+    /// Merkle root of message receipts in this block.
+    pub message_receipt_root: Bytes32,
+    /// Merkle root of all previous block header hashes.
+    pub prev_root: Bytes32,
+    /// Fuel block height.
+    pub height: BlockHeight,
+    /// The block producer time.
+    pub time: Tai64,
+    /// The nanoseconds of the block producer time.
+    #[cfg(feature = "nanos")]
+    pub time_nanos: u32,
+    /// Hash of the application header.
+    pub application_hash: Bytes32,
+    /// The id of the block.
+    pub id: BlockId,
+}
+
+/// The borrowed view of the [`BlockHeader`] fields with the C layout, for example,
+/// to pass the header through the FFI without copying the roots.
+///
+/// The scalar fields are copied, while the 32-byte fields point to the header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(C)]
+pub struct BlockHeaderView<'a> {
+    /// The version of the application header.
+    pub version: u8,
+    /// The layer 1 height of messages and events to include since the last layer 1 block number.
+    pub da_height: u64,
+    /// The layer 1 chain of the `da_height`.
+    #[cfg(feature = "multi-da")]
+    pub da_chain: u64,
+    /// Number of transactions in this block.
+    pub transactions_count: u64,
+    /// Number of message receipts in this block.
+    pub message_receipt_count: u64,
+    /// Merkle root of transactions.
+    pub transactions_root: &'a [u8; 32],
+    /// Merkle root of message receipts in this block.
+    pub message_receipt_root: &'a [u8; 32],
+    /// Merkle root of all previous block header hashes.
+    pub prev_root: &'a [u8; 32],
+    /// Fuel block height.
+    pub height: u32,
+    /// The block producer time as the TAI64 label.
+    pub time: u64,
+    /// The nanoseconds of the block producer time.
+    #[cfg(feature = "nanos")]
+    pub time_nanos: u32,
+    /// Hash of the application header.
+    pub application_hash: &'a [u8; 32],
+}
+
+impl BlockHeaderMetadata {
+    /// Creates the metadata with the cached `id` of the header.
+    ///
+    /// ```
+    /// use fuel_core_types::blockchain::{
+    ///     header::BlockHeaderMetadata,
+    ///     primitives::BlockId,
+    /// };
+    ///
+    /// let id = BlockId::from([1u8; 32]);
+    /// let metadata = BlockHeaderMetadata::new(id);
+    /// assert_eq!(metadata.id(), id);
+    /// ```
+    pub fn new(id: BlockId) -> Self {
+        Self { id }
+    }
+
+    /// The cached hash of the header.
+    pub fn id(&self) -> BlockId {
+        self.id
+    }
+}
+
+/// The size of the `da_chain` in the encodings, if the `multi-da` feature is enabled.
+const DA_CHAIN_SIZE: usize = if cfg!(feature = "multi-da") { 8 } else { 0 };
+/// The size of the `time_nanos` in the encodings, if the `nanos` feature is enabled.
+const TIME_NANOS_SIZE: usize = if cfg!(feature = "nanos") { 4 } else { 0 };
+
+/// The size of the canonical encoding of the [`BlockHeader`]. It includes
+/// the `da_chain` with the `multi-da` feature and the `time_nanos` with
+/// the `nanos` feature.
+pub const CANONICAL_HEADER_SIZE: usize = 165 + DA_CHAIN_SIZE + TIME_NANOS_SIZE;
+
+/// The size of the [`BlockHeader::sort_key`].
+pub const SORT_KEY_SIZE: usize = 40;
+
+/// The size of the compact encoding of the empty [`BlockHeader`]. It includes
+/// the same optional fields as the [`CANONICAL_HEADER_SIZE`].
+pub const COMPACT_HEADER_SIZE: usize = 53 + DA_CHAIN_SIZE + TIME_NANOS_SIZE;
+
+impl TryFrom<u8> for HeaderVersion {
+    type Error = CanonicalBytesError;
+
+    fn try_from(version: u8) -> Result<Self, Self::Error> {
+        match version {
+            0 => Ok(HeaderVersion::V0),
+            1 => Ok(HeaderVersion::V1),
+            unknown => Err(CanonicalBytesError::UnknownVersion(unknown)),
+        }
+    }
+}
+
 #[cfg(any(test, feature = "test-helpers"))]
 impl Default for BlockHeader {
     fn default() -> Self {
@@ -137,6 +731,23 @@ impl BlockHeader {
         default.recalculate_metadata();
         default
     }
+
+    /// Creates the partial header of the next block to extend test chains:
+    /// the height is one higher, the `prev_root` folds in the id of this header,
+    /// and the time is one second later, like in the
+    /// [`test_helpers::chain_of`]. The version and the `da_height` are the same
+    /// as in this header.
+    ///
+    /// The `accumulator` must contain the ids of all blocks before this header,
+    /// because the `prev_root` is the root of all previous blocks, not only
+    /// of this one. The method should be used only for tests.
+    pub fn with_next_height(&self, accumulator: &PrevRootBuilder) -> PartialBlockHeader {
+        let mut partial = self.to_partial();
+        partial.consensus.height = self.next_height().expect("The height overflowed");
+        partial.consensus.prev_root = self.next_prev_root(accumulator);
+        partial.consensus.time = advance_time(self.time(), 1);
+        partial
+    }
 }
 
 // Accessors for the consensus header.
@@ -149,10 +760,66 @@ impl BlockHeader {
     pub fn height(&self) -> &BlockHeight {
         &self.as_ref().height
     }
+    /// Fuel block height as `u32`, the type of heights in the VM.
+    ///
+    /// The conversion is lossless because the [`BlockHeight`] wraps `u32`.
+    /// It should become fallible if the [`BlockHeight`] is ever widened.
+    pub fn height_u32(&self) -> u32 {
+        **self.height()
+    }
+    /// The layer 1 height of the block as `u64`, for example, for metrics.
+    pub fn da_height_u64(&self) -> u64 {
+        self.application.da_height.0
+    }
+    /// Returns `true` if it is the header of the genesis block: the height is zero,
+    /// and the previous root is zeroed because there are no previous blocks.
+    pub fn is_genesis(&self) -> bool {
+        self.height() == &BlockHeight::default() && self.prev_root() == &Bytes32::zeroed()
+    }
+    /// The height of the previous block, or `None` for the block at zero height.
+    pub fn prev_height(&self) -> Option<BlockHeight> {
+        self.height().checked_sub(1).map(Into::into)
+    }
+    /// The height of the next block, or `None` for the block at the maximum height.
+    pub fn next_height(&self) -> Option<BlockHeight> {
+        self.height().checked_add(1).map(Into::into)
+    }
     /// The block producer time.
     pub fn time(&self) -> Tai64 {
         self.as_ref().time
     }
+    /// The block producer time with the nanosecond resolution.
+    #[cfg(feature = "nanos")]
+    pub fn time_n(&self) -> Tai64N {
+        Tai64N(self.as_ref().time, self.as_ref().time_nanos)
+    }
+    /// The block producer time as the number of seconds since the Unix epoch.
+    /// The time before the Unix epoch is negative.
+    pub fn unix_timestamp(&self) -> i64 {
+        self.time().to_unix()
+    }
+    /// The number of seconds since the time of the `parent`.
+    /// It is negative if the `parent` is later than the header.
+    pub fn time_since(&self, parent: &BlockHeader) -> i64 {
+        // The valid TAI64 labels are less than `2^63`, so the difference fits into `i64`.
+        self.time().0.wrapping_sub(parent.time().0) as i64
+    }
+    /// Returns `true` if the time is strictly later than the time of the `other`,
+    /// e.g., for the headers of the same PoA producer that mustn't repeat the time.
+    ///
+    /// Unlike the [`ConsensusHeader::verify_time_progression`], the equal times
+    /// are rejected.
+    pub fn time_strictly_after(&self, other: &BlockHeader) -> bool {
+        self.time() > other.time()
+    }
+    /// Returns `true` if the time is at most `max_age_secs` before the `now`,
+    /// e.g., to drop the stale headers from the gossip.
+    ///
+    /// The header from the future is fresh; use the
+    /// [`PartialBlockHeaderBuilder::time_checked`] to limit the future time.
+    pub fn is_fresh(&self, now: Tai64, max_age_secs: u64) -> bool {
+        now.0.saturating_sub(self.time().0) <= max_age_secs
+    }
     /// The hash of the application header.
     pub fn application_hash(&self) -> &Bytes32 {
         &self.as_ref().application_hash
@@ -162,6 +829,23 @@ impl BlockHeader {
     pub fn consensus_type(&self) -> ConsensusType {
         ConsensusType::PoA
     }
+
+    /// The fields of the application header generated during the execution.
+    ///
+    /// ```
+    /// use fuel_core_types::blockchain::header::PartialBlockHeaderBuilder;
+    ///
+    /// let header = PartialBlockHeaderBuilder::new().build().generate(&[], &[]);
+    ///
+    /// let generated = header.generated_application();
+    /// assert_eq!(generated.transactions_count, 0);
+    /// assert_eq!(generated.message_receipt_count, 0);
+    /// assert_eq!(generated.transactions_root, header.transactions_root);
+    /// assert_eq!(generated.message_receipt_root, header.message_receipt_root);
+    /// ```
+    pub fn generated_application(&self) -> &GeneratedApplicationFields {
+        &self.application.generated
+    }
 }
 
 /// Accessors for the consensus header.
@@ -175,13 +859,21 @@ impl PartialBlockHeader {
         &self.as_ref().height
     }
     /// The block producer time.
-    pub fn time(&self) -> &Tai64 {
-        &self.as_ref().time
+    pub fn time(&self) -> Tai64 {
+        self.as_ref().time
     }
     /// The type of consensus this header is using.
     pub fn consensus_type(&self) -> ConsensusType {
         ConsensusType::PoA
     }
+    /// The layer 1 height of messages and events to include.
+    pub fn da_height(&self) -> DaBlockHeight {
+        self.application.da_height
+    }
+    /// Sets the layer 1 height of messages and events to include.
+    pub fn set_da_height(&mut self, da_height: DaBlockHeight) {
+        self.application.da_height = da_height;
+    }
 }
 
 impl BlockHeader {
@@ -192,6 +884,29 @@ impl BlockHeader {
         self.metadata = Some(BlockHeaderMetadata { id: self.hash() });
     }
 
+    /// Generates the header metadata only if it is not populated yet.
+    ///
+    /// Unlike [`BlockHeader::recalculate_metadata`], which always recalculates
+    /// the hashes, it is free when the metadata is already cached.
+    pub fn ensure_metadata(&mut self) {
+        if self.metadata.is_none() {
+            self.recalculate_metadata();
+        }
+    }
+
+    /// Drops the cached metadata, e.g., to make the state of the cache explicit
+    /// before the serialization that skips it.
+    pub fn without_metadata(mut self) -> Self {
+        self.metadata = None;
+        self
+    }
+
+    /// Re-generates the cached metadata, e.g., after the deserialization.
+    pub fn with_recalculated_metadata(mut self) -> Self {
+        self.recalculate_metadata();
+        self
+    }
+
     /// Get the hash of the fuel header.
     pub fn hash(&self) -> BlockId {
         // The `BlockHeader` can be created only via the [`PartialBlockHeader::generate`] method,
@@ -204,6 +919,42 @@ impl BlockHeader {
         self.consensus.hash()
     }
 
+    /// Convert the header back into the [`PartialBlockHeader`], for example,
+    /// to re-execute the block.
+    ///
+    /// The conversion is lossy: the roots, the counts, the application hash and
+    /// the metadata are discarded. They are generated again by
+    /// [`PartialBlockHeader::generate`].
+    pub fn into_partial(self) -> PartialBlockHeader {
+        self.to_partial()
+    }
+
+    /// The same as [`BlockHeader::into_partial`], but doesn't consume the header.
+    pub fn to_partial(&self) -> PartialBlockHeader {
+        PartialBlockHeader {
+            application: ApplicationHeader {
+                version: self.application.version,
+                da_height: self.application.da_height,
+                #[cfg(feature = "multi-da")]
+                da_chain: self.application.da_chain,
+                generated: Empty {},
+            },
+            consensus: ConsensusHeader {
+                prev_root: self.consensus.prev_root,
+                height: self.consensus.height,
+                time: self.consensus.time,
+                #[cfg(feature = "nanos")]
+                time_nanos: self.consensus.time_nanos,
+                generated: Empty {},
+            },
+        }
+    }
+
+    /// Get the cached metadata of the header, if it is populated.
+    pub fn metadata(&self) -> Option<&BlockHeaderMetadata> {
+        self.metadata.as_ref()
+    }
+
     /// Get the cached fuel header hash.
     pub fn id(&self) -> BlockId {
         if let Some(ref metadata) = self.metadata {
@@ -213,48 +964,843 @@ impl BlockHeader {
         }
     }
 
-    /// Validate the transactions match the header.
-    pub fn validate_transactions(&self, transactions: &[Transaction]) -> bool {
-        // Generate the transaction merkle root.
-        let transactions_root = generate_txns_root(transactions);
+    /// The first 8 hex characters of the [`BlockHeader::id`] for logs.
+    ///
+    /// Different headers may have the same short id, so it must be used only
+    /// for the display.
+    pub fn short_id(&self) -> String {
+        let mut id = format!("{:x}", self.id());
+        id.truncate(8);
+        id
+    }
 
-        transactions_root == self.application.transactions_root
+    /// The key for key-value stores that sorts the headers by the height and
+    /// then by the id in the lexicographic order of bytes.
+    ///
+    /// The layout is the big-endian height widened to `u64` in the first 8 bytes,
+    /// followed by the 32 bytes of the [`BlockHeader::id`]. The height is widened
+    /// so the layout doesn't change if the [`BlockHeight`] is ever widened.
+    pub fn sort_key(&self) -> [u8; SORT_KEY_SIZE] {
+        let mut key = [0u8; SORT_KEY_SIZE];
+        key[..8].copy_from_slice(&u64::from(self.height_u32()).to_be_bytes());
+        key[8..].copy_from_slice(self.id().as_slice());
+        key
     }
-}
 
-impl PartialBlockHeader {
-    /// Generate all fields to create a full [`BlockHeader`]
-    /// after running the transactions.
+    /// Returns `true` if all fields of the header are zeroed, like in
+    /// the uninitialized `BlockHeader::default()`: the counts, heights, and
+    /// roots are zero, the version is [`HeaderVersion::V0`], and the time is
+    /// [`Tai64::UNIX_EPOCH`].
     ///
-    /// The order of the transactions must be the same order they were
-    /// executed in.
-    /// The order of the messages must be the same as they were
-    /// produced in.
+    /// The metadata is not checked because it is not a part of the hashed
+    /// fields, and the `application_hash` is not
+    /// checked because it is derived from the checked fields.
+    pub fn is_default(&self) -> bool {
+        let application = &self.application;
+        let consensus = &self.consensus;
+        #[cfg(feature = "multi-da")]
+        if application.da_chain != DaChainId::default() {
+            return false
+        }
+        #[cfg(feature = "nanos")]
+        if consensus.time_nanos != 0 {
+            return false
+        }
+        application.version == HeaderVersion::V0
+            && application.da_height == DaBlockHeight::default()
+            && application.transactions_count == 0
+            && application.message_receipt_count == 0
+            && application.transactions_root == Bytes32::zeroed()
+            && application.message_receipt_root == Bytes32::zeroed()
+            && consensus.prev_root == Bytes32::zeroed()
+            && consensus.height == BlockHeight::default()
+            && consensus.time == Tai64::UNIX_EPOCH
+    }
+
+    /// Returns `true` if the [`BlockHeader::id`] starts with at least
+    /// `leading_zero_bits` zero bits, in the big-endian order of bits.
     ///
-    /// Message ids are produced by executed the transactions and collecting
-    /// the ids from the receipts of messages outputs.
+    /// The id has 256 bits, so a larger `leading_zero_bits` is never met.
+    pub fn id_meets_difficulty(&self, leading_zero_bits: u32) -> bool {
+        let mut zeros = 0;
+        for byte in self.id().as_slice() {
+            zeros += byte.leading_zeros();
+            if *byte != 0 {
+                break
+            }
+        }
+        zeros >= leading_zero_bits
+    }
+
+    /// Returns which fields differ between the header and the `other`,
+    /// e.g., to debug the disagreement of nodes about the block.
     ///
-    /// The transactions are the bytes of the executed [`Transaction`]s.
+    /// The cached metadata is ignored.
+    pub fn diff(&self, other: &BlockHeader) -> HeaderDiff {
+        let (application, other_application) = (&self.application, &other.application);
+        let (consensus, other_consensus) = (&self.consensus, &other.consensus);
+
+        #[cfg(not(feature = "multi-da"))]
+        let da_height = application.da_height != other_application.da_height;
+        #[cfg(feature = "multi-da")]
+        let da_height = (application.da_height, application.da_chain)
+            != (other_application.da_height, other_application.da_chain);
+        #[cfg(not(feature = "nanos"))]
+        let time = consensus.time != other_consensus.time;
+        #[cfg(feature = "nanos")]
+        let time = self.time_n() != other.time_n();
+
+        HeaderDiff {
+            version: application.version != other_application.version,
+            da_height,
+            generated: application
+                .generated
+                .fields_diff(&other_application.generated),
+            prev_root: consensus.prev_root != other_consensus.prev_root,
+            height: consensus.height != other_consensus.height,
+            time,
+            application_hash: consensus.generated.application_hash
+                != other_consensus.generated.application_hash,
+        }
+    }
+
+    /// Returns `true` if the header and the `other` are different blocks
+    /// at the same height, i.e., the chain forked at this height.
+    pub fn is_sibling_of(&self, other: &BlockHeader) -> bool {
+        self.height() == other.height() && self.id() != other.id()
+    }
+
+    /// Returns `true` if the header is the direct child of the `parent`: its height
+    /// is the next after the height of the `parent`, and its `prev_root` is the root
+    /// after appending the id of the `parent` to the `accumulator`.
+    ///
+    /// The `accumulator` must contain the ids of all blocks before the `parent`.
+    pub fn links_to(&self, parent: &BlockHeader, accumulator: &PrevRootBuilder) -> bool {
+        // The heights must be consecutive; gaps are not allowed.
+        if parent.next_height() != Some(*self.height()) {
+            return false
+        }
+        self.consensus.verify_prev_root(&parent.id(), accumulator)
+    }
+
+    /// The `prev_root` of the child of this header: the root after appending
+    /// the id of this header to the `accumulator`.
+    ///
+    /// The `accumulator` must contain the ids of all blocks before this header.
+    pub fn next_prev_root(&self, accumulator: &PrevRootBuilder) -> Bytes32 {
+        accumulator.root_with(&self.id())
+    }
+
+    /// The range of the layer 1 heights covered by this block since the `parent`.
+    ///
+    /// The `da_height` is the layer 1 height of messages and events included
+    /// since the `da_height` of the parent, so the range includes both ends.
+    /// If the heights are equal, the range contains a single height.
+    /// The range is empty if the `da_height` is lower than the one of the `parent`;
+    /// see [`ApplicationHeader::verify_da_progression`].
+    pub fn da_height_range(
+        &self,
+        parent: &BlockHeader,
+    ) -> core::ops::RangeInclusive<DaBlockHeight> {
+        parent.da_height..=self.da_height
+    }
+
+    /// Calculate the fuel header hash, ignoring the cached metadata.
+    ///
+    /// Unlike [`BlockHeader::id`], it always recalculates the hash, so it can
+    /// be used to check that the cache is coherent.
+    pub fn compute_id(&self) -> BlockId {
+        self.hash()
+    }
+
+    /// Get the [`HeaderSummary`] of the header.
+    pub fn summary(&self) -> HeaderSummary {
+        HeaderSummary {
+            id: self.id(),
+            height: self.consensus.height,
+            prev_root: self.consensus.prev_root,
+        }
+    }
+
+    /// Get the borrowed [`BlockHeaderView`] of the header.
+    pub fn view(&self) -> BlockHeaderView<'_> {
+        BlockHeaderView {
+            version: self.application.version as u8,
+            da_height: self.application.da_height.0,
+            #[cfg(feature = "multi-da")]
+            da_chain: self.application.da_chain.0,
+            transactions_count: self.transactions_count,
+            message_receipt_count: self.message_receipt_count,
+            transactions_root: &self.application.generated.transactions_root,
+            message_receipt_root: &self.application.generated.message_receipt_root,
+            prev_root: &self.consensus.prev_root,
+            height: *self.consensus.height,
+            time: self.consensus.time.0,
+            #[cfg(feature = "nanos")]
+            time_nanos: self.consensus.time_nanos,
+            application_hash: &self.consensus.generated.application_hash,
+        }
+    }
+
+    /// Validate that the header is internally consistent: the roots of the empty
+    /// transactions and messages are the roots of the empty tree, the
+    /// `message_receipt_count` doesn't exceed the [`MAX_MESSAGES_PER_BLOCK`], the
+    /// `application_hash` matches the hash of the application header, and
+    /// the cached id, if any, matches the hash of the header.
+    pub fn validate(&self) -> Result<(), HeaderValidationError> {
+        let generated = &self.application.generated;
+        for (field, count, root) in [
+            (
+                "transactions_root",
+                generated.transactions_count,
+                generated.transactions_root,
+            ),
+            (
+                "message_receipt_root",
+                generated.message_receipt_count,
+                generated.message_receipt_root,
+            ),
+        ] {
+            if count == 0 && root != EMPTY_MERKLE_ROOT {
+                return Err(HeaderValidationError::CountRootMismatch { field, root })
+            }
+        }
+        if generated.message_receipt_count > MAX_MESSAGES_PER_BLOCK {
+            return Err(HeaderValidationError::TooManyMessages {
+                count: generated.message_receipt_count,
+                max: MAX_MESSAGES_PER_BLOCK,
+            })
+        }
+
+        let application_hash = self.application.hash();
+        if !ct_eq(self.consensus.application_hash, application_hash) {
+            return Err(HeaderValidationError::ApplicationHashMismatch {
+                expected: application_hash,
+                actual: self.consensus.application_hash,
+            })
+        }
+
+        if let Some(ref metadata) = self.metadata {
+            let id = self.consensus.hash();
+            if !ct_eq(metadata.id, id) {
+                return Err(HeaderValidationError::StaleMetadata {
+                    cached: metadata.id,
+                    actual: id,
+                })
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validates the header and wraps it into the [`ValidatedBlockHeader`].
+    pub fn into_validated(self) -> Result<ValidatedBlockHeader, HeaderValidationError> {
+        self.validate()?;
+        Ok(ValidatedBlockHeader(self))
+    }
+
+    /// The same as [`BlockHeader::validate`], but also validates that the header
+    /// at zero height is a well-formed genesis header: its `da_height` is zero,
+    /// and its `prev_root` is zeroed.
+    pub fn validate_genesis(&self) -> Result<(), HeaderValidationError> {
+        self.validate()?;
+
+        let (da_height, prev_root) = (self.da_height, *self.prev_root());
+        if self.height() == &BlockHeight::default()
+            && (da_height != DaBlockHeight::default() || prev_root != Bytes32::zeroed())
+        {
+            return Err(HeaderValidationError::GenesisConstraintViolated {
+                da_height,
+                prev_root,
+            })
+        }
+
+        Ok(())
+    }
+
+    /// Validate the transactions match the header.
+    pub fn validate_transactions(&self, transactions: &[Transaction]) -> bool {
+        // Generate the transaction merkle root.
+        let transactions_root = generate_txns_root(transactions);
+
+        transactions_root == self.application.transactions_root
+    }
+
+    /// Returns `true` if the `transactions_count` and the `transactions_root`
+    /// match the bytes of the executed `transactions`.
+    pub fn verify_transactions_root<Tx: AsRef<[u8]>>(&self, transactions: &[Tx]) -> bool {
+        if self.transactions_count != transactions.len() as u64 {
+            return false
+        }
+        let mut accumulator = TransactionRootAccumulator::new();
+        for tx_bytes in transactions {
+            accumulator.push(tx_bytes.as_ref());
+        }
+        ct_eq(accumulator.finalize(), self.transactions_root)
+    }
+
+    /// Returns `true` if the `message_receipt_count` and the `message_receipt_root`
+    /// match the `message_ids`.
+    pub fn verify_message_receipt_root(&self, message_ids: &[MessageId]) -> bool {
+        if self.message_receipt_count != message_ids.len() as u64 {
+            return false
+        }
+        let message_receipt_root: Bytes32 =
+            generate_messages_tree(message_ids).root().into();
+        ct_eq(message_receipt_root, self.message_receipt_root)
+    }
+}
+
+impl Default for PartialBlockHeaderBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BlockHeader {
+    /// Encodes the header into bytes that don't depend on the `serde` derive.
+    ///
+    /// The fields go in the same order as they are hashed: the application header
+    /// and then the consensus header. The `version` is encoded as a single byte,
+    /// numbers as big-endian bytes.
+    /// The cached metadata is not a part of the encoding.
+    pub fn to_canonical_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(CANONICAL_HEADER_SIZE);
+        bytes.push(self.application.version as u8);
+        bytes.extend_from_slice(&self.application.da_height.to_bytes());
+        #[cfg(feature = "multi-da")]
+        bytes.extend_from_slice(&self.application.da_chain.0.to_be_bytes());
+        bytes.extend_from_slice(&self.transactions_count.to_be_bytes());
+        bytes.extend_from_slice(&self.message_receipt_count.to_be_bytes());
+        bytes.extend_from_slice(self.transactions_root.as_ref());
+        bytes.extend_from_slice(self.message_receipt_root.as_ref());
+        bytes.extend_from_slice(self.consensus.prev_root.as_ref());
+        bytes.extend_from_slice(&self.consensus.height.to_bytes());
+        bytes.extend_from_slice(&self.consensus.time.0.to_be_bytes());
+        #[cfg(feature = "nanos")]
+        bytes.extend_from_slice(&self.consensus.time_nanos.to_be_bytes());
+        bytes.extend_from_slice(self.consensus.application_hash.as_ref());
+        bytes
+    }
+
+    /// The length of the [`BlockHeader::to_canonical_bytes`], to allocate the buffer
+    /// without encoding the header.
+    ///
+    /// All fields of the canonical encoding have the fixed size, so the hint is
+    /// always [`CANONICAL_HEADER_SIZE`]. The id is not a part of the encoding.
+    pub fn serialized_size_hint(&self) -> usize {
+        CANONICAL_HEADER_SIZE
+    }
+
+    /// Encodes the header of the empty block into the compact form. Returns `None`
+    /// if the block has transactions or messages.
+    ///
+    /// The compact form is the canonical encoding without the generated fields:
+    /// they are known for the empty block and are generated again by
+    /// [`BlockHeader::from_compact`].
+    pub fn try_to_compact(&self) -> Option<[u8; COMPACT_HEADER_SIZE]> {
+        let generated = &self.application.generated;
+        if generated.transactions_count != 0
+            || generated.message_receipt_count != 0
+            || generated.transactions_root != EMPTY_MERKLE_ROOT
+            || generated.message_receipt_root != EMPTY_MERKLE_ROOT
+        {
+            return None
+        }
+
+        let mut compact = [0u8; COMPACT_HEADER_SIZE];
+        let mut bytes = &mut compact[..];
+        put(&mut bytes, &[self.application.version as u8]);
+        put(&mut bytes, &self.application.da_height.to_bytes());
+        #[cfg(feature = "multi-da")]
+        put(&mut bytes, &self.application.da_chain.0.to_be_bytes());
+        put(&mut bytes, self.consensus.prev_root.as_ref());
+        put(&mut bytes, &self.consensus.height.to_bytes());
+        put(&mut bytes, &self.consensus.time.0.to_be_bytes());
+        #[cfg(feature = "nanos")]
+        put(&mut bytes, &self.consensus.time_nanos.to_be_bytes());
+        Some(compact)
+    }
+
+    /// Decodes the header of the empty block from the [`BlockHeader::try_to_compact`]
+    /// encoding. The decoded header has the metadata populated.
+    pub fn from_compact(
+        compact: &[u8; COMPACT_HEADER_SIZE],
+    ) -> Result<Self, CanonicalBytesError> {
+        let mut bytes = &compact[..];
+        let [version] = take(&mut bytes);
+        let da_height = u64::from_be_bytes(take(&mut bytes)).into();
+        #[cfg(feature = "multi-da")]
+        let da_chain = DaChainId(u64::from_be_bytes(take(&mut bytes)));
+        let prev_root = take(&mut bytes).into();
+        let height = take(&mut bytes).into();
+        let time = Tai64(u64::from_be_bytes(take(&mut bytes)));
+        #[cfg(feature = "nanos")]
+        let time_nanos = time_nanos_from_bytes(take(&mut bytes))?;
+
+        let partial = PartialBlockHeader {
+            application: ApplicationHeader {
+                version: version.try_into()?,
+                da_height,
+                #[cfg(feature = "multi-da")]
+                da_chain,
+                generated: Empty {},
+            },
+            consensus: ConsensusHeader {
+                prev_root,
+                height,
+                time,
+                #[cfg(feature = "nanos")]
+                time_nanos,
+                generated: Empty {},
+            },
+        };
+        Ok(partial.generate_with_roots(0, 0, EMPTY_MERKLE_ROOT, EMPTY_MERKLE_ROOT))
+    }
+
+    /// Decodes the header from the [`BlockHeader::to_canonical_bytes`] encoding.
+    /// The decoded header is validated and has the metadata populated.
+    pub fn from_canonical_bytes(mut bytes: &[u8]) -> Result<Self, CanonicalBytesError> {
+        if bytes.len() != CANONICAL_HEADER_SIZE {
+            return Err(CanonicalBytesError::InvalidLength(bytes.len()))
+        }
+
+        let [version] = take(&mut bytes);
+        let application = ApplicationHeader {
+            version: version.try_into()?,
+            da_height: u64::from_be_bytes(take(&mut bytes)).into(),
+            #[cfg(feature = "multi-da")]
+            da_chain: DaChainId(u64::from_be_bytes(take(&mut bytes))),
+            generated: GeneratedApplicationFields {
+                transactions_count: u64::from_be_bytes(take(&mut bytes)),
+                message_receipt_count: u64::from_be_bytes(take(&mut bytes)),
+                transactions_root: take(&mut bytes).into(),
+                message_receipt_root: take(&mut bytes).into(),
+            },
+        };
+        let prev_root = take(&mut bytes).into();
+        let height = take(&mut bytes).into();
+        let time = Tai64(u64::from_be_bytes(take(&mut bytes)));
+        #[cfg(feature = "nanos")]
+        let time_nanos = time_nanos_from_bytes(take(&mut bytes))?;
+        let consensus = ConsensusHeader {
+            prev_root,
+            height,
+            time,
+            #[cfg(feature = "nanos")]
+            time_nanos,
+            generated: GeneratedConsensusFields {
+                application_hash: take(&mut bytes).into(),
+            },
+        };
+
+        let mut header = BlockHeader {
+            application,
+            consensus,
+            metadata: None,
+        };
+        header.validate()?;
+        header.metadata = Some(BlockHeaderMetadata {
+            id: header.consensus.hash(),
+        });
+        Ok(header)
+    }
+}
+
+#[cfg(feature = "nanos")]
+fn time_nanos_from_bytes(bytes: [u8; 4]) -> Result<u32, CanonicalBytesError> {
+    let time_nanos = u32::from_be_bytes(bytes);
+    if time_nanos >= NANOS_PER_SECOND {
+        return Err(CanonicalBytesError::InvalidTimeNanos(time_nanos))
+    }
+    Ok(time_nanos)
+}
+
+/// Writes the `data` and advances the `bytes`. The caller must check the length
+/// of the `bytes`.
+fn put(bytes: &mut &mut [u8], data: &[u8]) {
+    let (head, tail) = core::mem::take(bytes).split_at_mut(data.len());
+    head.copy_from_slice(data);
+    *bytes = tail;
+}
+
+/// Takes the next `N` bytes. The caller must check the length of the `bytes`.
+fn take<const N: usize>(bytes: &mut &[u8]) -> [u8; N] {
+    let (head, tail) = bytes.split_at(N);
+    *bytes = tail;
+    head.try_into()
+        .expect("The length of the slice is `N` because of the `split_at`")
+}
+
+impl From<&BlockHeader> for BlockHeaderRow {
+    fn from(header: &BlockHeader) -> Self {
+        Self {
+            version: header.application.version,
+            da_height: header.application.da_height,
+            #[cfg(feature = "multi-da")]
+            da_chain: header.application.da_chain,
+            transactions_count: header.transactions_count,
+            message_receipt_count: header.message_receipt_count,
+            transactions_root: header.transactions_root,
+            message_receipt_root: header.message_receipt_root,
+            prev_root: header.consensus.prev_root,
+            height: header.consensus.height,
+            time: header.consensus.time,
+            #[cfg(feature = "nanos")]
+            time_nanos: header.consensus.time_nanos,
+            application_hash: header.consensus.application_hash,
+            id: header.id(),
+        }
+    }
+}
+
+impl From<BlockHeader> for u32 {
+    fn from(header: BlockHeader) -> Self {
+        header.height_u32()
+    }
+}
+
+impl TryFrom<BlockHeaderRow> for BlockHeader {
+    type Error = HeaderValidationError;
+
+    /// Rebuilds the header and validates the `application_hash` and the `id`
+    /// of the row.
+    fn try_from(row: BlockHeaderRow) -> Result<Self, Self::Error> {
+        let header = BlockHeader {
+            application: ApplicationHeader {
+                version: row.version,
+                da_height: row.da_height,
+                #[cfg(feature = "multi-da")]
+                da_chain: row.da_chain,
+                generated: GeneratedApplicationFields {
+                    transactions_count: row.transactions_count,
+                    message_receipt_count: row.message_receipt_count,
+                    transactions_root: row.transactions_root,
+                    message_receipt_root: row.message_receipt_root,
+                },
+            },
+            consensus: ConsensusHeader {
+                prev_root: row.prev_root,
+                height: row.height,
+                time: row.time,
+                #[cfg(feature = "nanos")]
+                time_nanos: row.time_nanos,
+                generated: GeneratedConsensusFields {
+                    application_hash: row.application_hash,
+                },
+            },
+            metadata: Some(BlockHeaderMetadata { id: row.id }),
+        };
+        header.validate()?;
+        Ok(header)
+    }
+}
+
+impl PartialBlockHeader {
+    /// Creates the canonical partial header of the genesis block: the height
+    /// is zero, and the `prev_root` is the zeroed hash. Other fields are the same
+    /// as in the [`PartialBlockHeaderBuilder::new`].
+    ///
+    /// The [`PartialBlockHeader::generate`] must still be called with
+    /// the transactions and the messages of the genesis block. The resulting header
+    /// passes the [`BlockHeader::validate_genesis`] only with the zero `da_height`.
+    pub fn genesis(da_height: DaBlockHeight, time: Tai64) -> Self {
+        PartialBlockHeaderBuilder::new()
+            .da_height(da_height)
+            .prev_root(Bytes32::zeroed())
+            .height(BlockHeight::default())
+            .time(time)
+            .build()
+    }
+}
+
+impl PartialBlockHeaderBuilder {
+    /// Creates the builder of the [`PartialBlockHeader`].
+    pub fn new() -> Self {
+        Self {
+            header: PartialBlockHeader {
+                application: ApplicationHeader {
+                    version: HeaderVersion::default(),
+                    da_height: DaBlockHeight::default(),
+                    #[cfg(feature = "multi-da")]
+                    da_chain: DaChainId::default(),
+                    generated: Empty,
+                },
+                consensus: ConsensusHeader {
+                    prev_root: Bytes32::zeroed(),
+                    height: BlockHeight::default(),
+                    time: Tai64::UNIX_EPOCH,
+                    #[cfg(feature = "nanos")]
+                    time_nanos: 0,
+                    generated: Empty,
+                },
+            },
+        }
+    }
+
+    /// Sets the version of the header format.
+    pub fn version(&mut self, version: HeaderVersion) -> &mut Self {
+        self.header.application.version = version;
+        self
+    }
+
+    /// Sets the layer 1 height of messages and events to include.
+    pub fn da_height(&mut self, da_height: DaBlockHeight) -> &mut Self {
+        self.header.application.da_height = da_height;
+        self
+    }
+
+    /// Sets the layer 1 chain of the `da_height`.
+    #[cfg(feature = "multi-da")]
+    pub fn da_chain(&mut self, da_chain: DaChainId) -> &mut Self {
+        self.header.application.da_chain = da_chain;
+        self
+    }
+
+    /// Sets the merkle root of all previous block header hashes.
+    pub fn prev_root(&mut self, prev_root: Bytes32) -> &mut Self {
+        self.header.consensus.prev_root = prev_root;
+        self
+    }
+
+    /// Sets the fuel block height.
+    pub fn height(&mut self, height: BlockHeight) -> &mut Self {
+        self.header.consensus.height = height;
+        self
+    }
+
+    /// Sets the fuel block height from the `u32` height of the VM.
+    pub fn height_u32(&mut self, height: u32) -> &mut Self {
+        self.height(height.into())
+    }
+
+    /// Sets the block producer time.
+    pub fn time(&mut self, time: Tai64) -> &mut Self {
+        self.header.consensus.time = time;
+        self
+    }
+
+    /// Sets the block producer time with the nanosecond resolution.
+    ///
+    /// The [`PartialBlockHeaderBuilder::time`] only sets the seconds and keeps
+    /// the nanoseconds.
+    #[cfg(feature = "nanos")]
+    pub fn time_n(&mut self, time: Tai64N) -> &mut Self {
+        self.header.consensus.time = time.0;
+        self.header.consensus.time_nanos = time.1;
+        self
+    }
+
+    /// Sets the block producer time if it is at most `max_future_secs` ahead
+    /// of the `now`. Otherwise, returns an error and doesn't change the time.
+    pub fn time_checked(
+        &mut self,
+        time: Tai64,
+        now: Tai64,
+        max_future_secs: u64,
+    ) -> Result<&mut Self, TimeError> {
+        if time.0.saturating_sub(now.0) > max_future_secs {
+            return Err(TimeError::TooFarInFuture {
+                now,
+                time,
+                max_future_secs,
+            })
+        }
+        Ok(self.time(time))
+    }
+
+    /// Sets the block producer time from the number of seconds since the Unix epoch.
+    /// The time before the Unix epoch is negative.
+    pub fn unix_timestamp(&mut self, secs: i64) -> &mut Self {
+        self.time(Tai64::from_unix(secs))
+    }
+
+    /// Builds the [`PartialBlockHeader`].
+    pub fn build(&self) -> PartialBlockHeader {
+        self.header.clone()
+    }
+}
+
+impl PartialBlockHeader {
+    /// Generate all fields to create a full [`BlockHeader`]
+    /// after running the transactions.
+    ///
+    /// The order of the transactions must be the same order they were
+    /// executed in.
+    /// The order of the messages must be the same as they were
+    /// produced in.
+    ///
+    /// Message ids are produced by executed the transactions and collecting
+    /// the ids from the receipts of messages outputs.
+    ///
+    /// The transactions are the bytes of the executed [`Transaction`]s.
     pub fn generate(
         self,
         transactions: &[Transaction],
         message_ids: &[MessageId],
     ) -> BlockHeader {
-        // Generate the transaction merkle root.
-        let transactions_root = generate_txns_root(transactions);
+        let GeneratedApplicationFields {
+            transactions_count,
+            message_receipt_count,
+            transactions_root,
+            message_receipt_root,
+        } = generate_application_fields(transactions, message_ids);
 
-        // Generate the message merkle root.
+        self.generate_with_roots(
+            transactions_count,
+            message_receipt_count,
+            transactions_root,
+            message_receipt_root,
+        )
+    }
+
+    /// The same as [`PartialBlockHeader::generate`], but the leaves of
+    /// the `transactions_root` are the `tx_ids` instead of the bytes of
+    /// the transactions.
+    ///
+    /// # Warning
+    ///
+    /// The canonical `transactions_root` of the specification is the root of
+    /// the bytes of the executed transactions, so the root generated by this
+    /// method is **not** canonical: it differs from the root of
+    /// [`PartialBlockHeader::generate`] for the same transactions, and the header
+    /// doesn't pass [`BlockHeader::validate_transactions`]. The ids and bytes
+    /// can't be told apart at runtime, so the method must only be used where
+    /// the whole system agrees on the roots over the ids.
+    pub fn generate_from_tx_ids(
+        self,
+        tx_ids: &[Bytes32],
+        message_ids: &[MessageId],
+    ) -> BlockHeader {
+        debug_assert_eq!(
+            check_counts(tx_ids.len(), message_ids.len()),
+            Ok(()),
+            "The counts are truncated; use `PartialBlockHeader::try_generate`"
+        );
+
+        let transactions_root = if tx_ids.is_empty() {
+            EMPTY_MERKLE_ROOT
+        } else {
+            let mut tree = fuel_merkle::binary::in_memory::MerkleTree::new();
+            for id in tx_ids {
+                tree.push(id.as_ref());
+            }
+            tree.root().into()
+        };
+        let message_receipt_root = if message_ids.is_empty() {
+            EMPTY_MERKLE_ROOT
+        } else {
+            generate_messages_tree(message_ids).root().into()
+        };
+
+        self.generate_with_roots(
+            tx_ids.len() as u64,
+            message_ids.len() as u64,
+            transactions_root,
+            message_receipt_root,
+        )
+    }
+
+    /// Calculate the application hash of the block with these `transactions`
+    /// and `message_ids` without creating the full [`BlockHeader`].
+    ///
+    /// The result is the same as [`BlockHeader::application_hash`] of the header
+    /// returned by [`PartialBlockHeader::generate`] for the same inputs.
+    pub fn application_hash(
+        &self,
+        transactions: &[Transaction],
+        message_ids: &[MessageId],
+    ) -> Bytes32 {
+        let application = ApplicationHeader {
+            version: self.application.version,
+            da_height: self.application.da_height,
+            #[cfg(feature = "multi-da")]
+            da_chain: self.application.da_chain,
+            generated: generate_application_fields(transactions, message_ids),
+        };
+        application.hash()
+    }
+
+    /// The same as [`PartialBlockHeader::generate`], but returns an error
+    /// if the number of `transactions` or `message_ids` doesn't fit into `u64`
+    /// instead of truncating it.
+    pub fn try_generate(
+        self,
+        transactions: &[Transaction],
+        message_ids: &[MessageId],
+    ) -> Result<BlockHeader, GenerateError> {
+        check_counts(transactions.len(), message_ids.len())?;
+        Ok(self.generate(transactions, message_ids))
+    }
+
+    /// The same as [`PartialBlockHeader::try_generate`], but also returns an error
+    /// if the `message_ids` contain duplicates, because the message ids
+    /// are unique per block.
+    pub fn generate_checked(
+        self,
+        transactions: &[Transaction],
+        message_ids: &[MessageId],
+    ) -> Result<BlockHeader, GenerateError> {
+        let mut unique = std::collections::HashSet::with_capacity(message_ids.len());
+        if let Some(duplicate) = message_ids.iter().find(|id| !unique.insert(*id)) {
+            return Err(GenerateError::DuplicateMessageId(*duplicate))
+        }
+        self.try_generate(transactions, message_ids)
+    }
+
+    /// The same as [`PartialBlockHeader::generate`], but consumes the iterators
+    /// without collecting them.
+    ///
+    /// The `transactions` are the bytes of the executed [`Transaction`]s.
+    pub fn generate_from_iter(
+        self,
+        transactions: impl Iterator<Item = impl AsRef<[u8]>>,
+        message_ids: impl Iterator<Item = MessageId>,
+    ) -> BlockHeader {
+        let mut transactions_count = 0u64;
+        let mut transactions_root = TransactionRootAccumulator::new();
+        for tx_bytes in transactions {
+            transactions_root.push(tx_bytes.as_ref());
+            transactions_count += 1;
+        }
+
+        let mut message_receipt_count = 0u64;
         let mut message_tree = fuel_merkle::binary::in_memory::MerkleTree::new();
         for id in message_ids {
             message_tree.push(id.as_ref());
+            message_receipt_count += 1;
         }
-        let message_receipt_root = message_tree.root().into();
 
+        self.generate_with_roots(
+            transactions_count,
+            message_receipt_count,
+            transactions_root.finalize(),
+            message_tree.root().into(),
+        )
+    }
+
+    /// Create a full [`BlockHeader`] from already known application roots,
+    /// skipping the construction of the merkle trees.
+    ///
+    /// It is useful when the roots are taken from a trusted source, for example,
+    /// from the proposed header during re-execution. The caller is responsible
+    /// for the correctness of the roots; they are not verified against the
+    /// transactions or the messages of the block.
+    pub fn generate_with_roots(
+        self,
+        transactions_count: u64,
+        message_receipt_count: u64,
+        transactions_root: Bytes32,
+        message_receipt_root: Bytes32,
+    ) -> BlockHeader {
         let application = ApplicationHeader {
+            version: self.application.version,
             da_height: self.application.da_height,
+            #[cfg(feature = "multi-da")]
+            da_chain: self.application.da_chain,
             generated: GeneratedApplicationFields {
-                transactions_count: transactions.len() as u64,
-                message_receipt_count: message_ids.len() as u64,
+                transactions_count,
+                message_receipt_count,
                 transactions_root,
                 message_receipt_root,
             },
@@ -266,6 +1812,8 @@ impl PartialBlockHeader {
                 prev_root: self.consensus.prev_root,
                 height: self.consensus.height,
                 time: self.consensus.time,
+                #[cfg(feature = "nanos")]
+                time_nanos: self.consensus.time_nanos,
                 generated: GeneratedConsensusFields {
                     // Calculates it inside of `BlockHeader::recalculate_metadata`.
                     application_hash: Default::default(),
@@ -280,43 +1828,835 @@ impl PartialBlockHeader {
     }
 }
 
+/// Generate the merkle root of the `transactions` and the proof of inclusion
+/// of the transaction at the `index`.
+///
+/// The proof can be verified against the [`GeneratedApplicationFields::transactions_root`]
+/// of the block with these `transactions`. Returns `None` if the `index` is out of range.
+pub fn transactions_proof(
+    transactions: &[Transaction],
+    index: usize,
+) -> Option<(Bytes32, MerkleProof)> {
+    let proof_index = index as u64;
+    let (root, proof_set) = generate_txns_tree(transactions).prove(proof_index)?;
+    Some((
+        root.into(),
+        MerkleProof {
+            proof_set,
+            proof_index,
+        },
+    ))
+}
+
+/// Generate the merkle root of the `transactions` and the proofs of inclusion
+/// of the transactions at the `indices`, building the tree only once.
+///
+/// The duplicated indices are removed, and the proofs are returned in the ascending
+/// order of indices. Returns `None` if any index is out of range.
+pub fn transactions_multiproof(
+    transactions: &[Transaction],
+    indices: &[usize],
+) -> Option<(Bytes32, Vec<MerkleProof>)> {
+    let mut indices = indices.to_vec();
+    indices.sort_unstable();
+    indices.dedup();
+
+    let tree = generate_txns_tree(transactions);
+    let proofs = indices
+        .into_iter()
+        .map(|index| {
+            let proof_index = index as u64;
+            let (_, proof_set) = tree.prove(proof_index)?;
+            Some(MerkleProof {
+                proof_set,
+                proof_index,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    Some((tree.root().into(), proofs))
+}
+
+/// Generate the merkle root of the `message_ids` and the proof of inclusion
+/// of the message at the `index`.
+///
+/// The proof can be verified against the [`GeneratedApplicationFields::message_receipt_root`]
+/// of the block that produced these messages. Returns `None` if the `index` is out of range.
+pub fn message_receipts_proof(
+    message_ids: &[MessageId],
+    index: usize,
+) -> Option<(Bytes32, MerkleProof)> {
+    let proof_index = index as u64;
+    let (root, proof_set) = generate_messages_tree(message_ids).prove(proof_index)?;
+    Some((
+        root.into(),
+        MerkleProof {
+            proof_set,
+            proof_index,
+        },
+    ))
+}
+
+/// The root of the empty binary merkle tree, the same as
+/// `fuel_merkle::binary::in_memory::MerkleTree::new().root()`.
+///
+/// It is the `transactions_root` and the `message_receipt_root` of the empty
+/// blocks, which use it to avoid building the trees.
+pub const EMPTY_MERKLE_ROOT: Bytes32 =
+    Bytes32::new(*fuel_merkle::common::empty_sum_sha256());
+
+/// The largest `message_receipt_count` accepted by the [`BlockHeader::validate`].
+///
+/// The count comes from untrusted headers, so it is bounded to keep the message
+/// trees and their proofs reasonable. The [`message_receipts_proof`] takes
+/// the `usize` index, so it is also the largest count provable on 32-bit targets.
+pub const MAX_MESSAGES_PER_BLOCK: u64 = u32::MAX as u64;
+
+/// Checks that the number of transactions fits into `u64` and the number
+/// of messages doesn't exceed the [`MAX_MESSAGES_PER_BLOCK`].
+fn check_counts(
+    transactions_count: usize,
+    message_receipt_count: usize,
+) -> Result<(), GenerateError> {
+    if u64::try_from(transactions_count).is_err() {
+        return Err(GenerateError::TooManyTransactions(transactions_count))
+    }
+    if u64::try_from(message_receipt_count)
+        .map_or(true, |count| count > MAX_MESSAGES_PER_BLOCK)
+    {
+        return Err(GenerateError::TooManyMessages(message_receipt_count))
+    }
+    Ok(())
+}
+
+fn generate_application_fields(
+    transactions: &[Transaction],
+    message_ids: &[MessageId],
+) -> GeneratedApplicationFields {
+    debug_assert_eq!(
+        check_counts(transactions.len(), message_ids.len()),
+        Ok(()),
+        "The counts are truncated; use `PartialBlockHeader::try_generate`"
+    );
+
+    // Generate the transaction merkle root.
+    let transactions_root = if transactions.is_empty() {
+        EMPTY_MERKLE_ROOT
+    } else {
+        generate_txns_root(transactions)
+    };
+
+    // Generate the message merkle root.
+    let message_receipt_root = if message_ids.is_empty() {
+        EMPTY_MERKLE_ROOT
+    } else {
+        generate_messages_tree(message_ids).root().into()
+    };
+
+    GeneratedApplicationFields {
+        transactions_count: transactions.len() as u64,
+        message_receipt_count: message_ids.len() as u64,
+        transactions_root,
+        message_receipt_root,
+    }
+}
+
+fn generate_messages_tree(
+    message_ids: &[MessageId],
+) -> fuel_merkle::binary::in_memory::MerkleTree {
+    let mut message_tree = fuel_merkle::binary::in_memory::MerkleTree::new();
+    for id in message_ids {
+        message_tree.push(id.as_ref());
+    }
+    message_tree
+}
+
+/// Accumulates the bytes of executed transactions one by one to calculate
+/// the [`GeneratedApplicationFields::transactions_root`] without holding
+/// all transactions of the block in memory.
+///
+/// Transactions should be pushed in the order they were executed in.
+#[derive(Clone, Debug, Default)]
+pub struct TransactionRootAccumulator {
+    tree: fuel_merkle::binary::in_memory::MerkleTree,
+}
+
+impl TransactionRootAccumulator {
+    /// Creates the accumulator without transactions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the bytes of the next executed [`Transaction`].
+    pub fn push(&mut self, tx_bytes: &[u8]) {
+        self.tree.push(tx_bytes);
+    }
+
+    /// The merkle root of all pushed transactions.
+    pub fn finalize(self) -> Bytes32 {
+        self.tree.root().into()
+    }
+}
+
+/// Returns `true` if the bytes of the transactions produce the `expected`
+/// [`GeneratedApplicationFields::transactions_root`].
+///
+/// The transactions are folded into the [`TransactionRootAccumulator`] one by one,
+/// so the bytes of each transaction can be dropped after it is read, for example,
+/// when the block is streamed from the disk.
+pub fn verify_transactions_root_streaming(
+    expected: &Bytes32,
+    txs: impl Iterator<Item = impl AsRef<[u8]>>,
+) -> bool {
+    let mut accumulator = TransactionRootAccumulator::new();
+    for tx_bytes in txs {
+        accumulator.push(tx_bytes.as_ref());
+    }
+    ct_eq(accumulator.finalize(), *expected)
+}
+
 fn generate_txns_root(transactions: &[Transaction]) -> Bytes32 {
+    generate_txns_tree(transactions).root().into()
+}
+
+fn generate_txns_tree(
+    transactions: &[Transaction],
+) -> fuel_merkle::binary::in_memory::MerkleTree {
     // TODO: The `to_bytes` requires mutability(but it is problem of the API).
     //  Remove `clone` when we can use `to_bytes` without mutability.
     let transaction_ids = transactions.iter().map(|tx| tx.clone().to_bytes());
-    // Generate the transaction merkle root.
+    // Generate the transaction merkle tree.
     let mut transaction_tree = fuel_merkle::binary::in_memory::MerkleTree::new();
     for id in transaction_ids {
         transaction_tree.push(id.as_ref());
     }
-    transaction_tree.root().into()
+    transaction_tree
 }
 
 impl ApplicationHeader<GeneratedApplicationFields> {
     /// Hash the application header.
+    ///
+    /// The preimage is, in order:
+    /// - `version` as a single byte, only since [`HeaderVersion::V1`];
+    /// - `da_height`, `da_chain` with the `multi-da` feature, `transactions_count`
+    ///   and `message_receipt_count` as 8 big-endian bytes each;
+    /// - `transactions_root` and `message_receipt_root`.
+    ///
+    /// A client can rebuild the application header from its fields and compare
+    /// the result with the hash committed by the producer:
+    ///
+    /// ```
+    /// use fuel_core_types::blockchain::header::{
+    ///     ApplicationHeader,
+    ///     GeneratedApplicationFields,
+    ///     PartialBlockHeaderBuilder,
+    /// };
+    ///
+    /// let header = PartialBlockHeaderBuilder::new()
+    ///     .da_height(10u64.into())
+    ///     .build()
+    ///     .generate(&[], &[]);
+    ///
+    /// let application = ApplicationHeader {
+    ///     version: header.application.version,
+    ///     da_height: header.da_height,
+    ///     # #[cfg(feature = "multi-da")]
+    ///     # da_chain: header.da_chain,
+    ///     generated: GeneratedApplicationFields {
+    ///         transactions_count: header.transactions_count,
+    ///         message_receipt_count: header.message_receipt_count,
+    ///         transactions_root: header.transactions_root,
+    ///         message_receipt_root: header.message_receipt_root,
+    ///     },
+    /// };
+    /// assert_eq!(&application.hash(), header.application_hash());
+    /// ```
     pub fn hash(&self) -> Bytes32 {
+        self.hash_with::<crate::fuel_crypto::Hasher>()
+    }
+
+    /// Hash the application header with the digest function of the `Hasher`.
+    ///
+    /// The preimage is the same as for the [`ApplicationHeader::hash`].
+    pub fn hash_with<Hasher: HeaderHasher>(&self) -> Bytes32 {
+        let mut hasher = Hasher::default();
+        self.input_preimage(|bytes| hasher.input(bytes));
+        hasher.finalize()
+    }
+
+    /// The exact bytes hashed by the [`ApplicationHeader::hash`], to compare
+    /// the preimages when the hashes of different implementations don't match.
+    pub fn hash_preimage(&self) -> Vec<u8> {
+        let mut preimage = vec![];
+        self.input_preimage(|bytes| preimage.extend_from_slice(bytes));
+        preimage
+    }
+
+    fn input_preimage(&self, mut input: impl FnMut(&[u8])) {
         // Order matters and is the same as the spec.
+        // The `V0` headers don't include the version to keep the hash
+        // of the existing blocks unchanged.
+        if self.version != HeaderVersion::V0 {
+            input(&[self.version as u8]);
+        }
+        input(&hash_encode_u64_be(self.da_height.0));
+        #[cfg(feature = "multi-da")]
+        input(&hash_encode_u64_be(self.da_chain.0));
+        input(&hash_encode_u64_be(self.transactions_count));
+        input(&hash_encode_u64_be(self.message_receipt_count));
+        input(self.transactions_root.as_ref());
+        input(self.message_receipt_root.as_ref());
+    }
+}
+
+impl GeneratedApplicationFields {
+    /// The number of levels of the transaction merkle tree, which is also
+    /// the maximum length of the proofs of inclusion.
+    ///
+    /// It is `ceil(log2(transactions_count))`, and zero for the empty block
+    /// and for the block with a single transaction.
+    pub fn transactions_tree_depth(&self) -> u32 {
+        match self.transactions_count {
+            0 | 1 => 0,
+            count => u64::BITS - (count - 1).leading_zeros(),
+        }
+    }
+
+    /// The commitment to both roots: the hash of the `transactions_root`
+    /// followed by the `message_receipt_root`.
+    ///
+    /// Unlike the [`ApplicationHeader::hash`], it doesn't commit to the counts
+    /// and the `da_height`.
+    pub fn combined_root(&self) -> Bytes32 {
         let mut hasher = crate::fuel_crypto::Hasher::default();
-        hasher.input(&self.da_height.to_bytes()[..]);
-        hasher.input(self.transactions_count.to_be_bytes());
-        hasher.input(self.message_receipt_count.to_be_bytes());
         hasher.input(self.transactions_root.as_ref());
         hasher.input(self.message_receipt_root.as_ref());
         hasher.digest()
     }
+
+    /// Returns `true` if both the `transactions_root` and
+    /// the `message_receipt_root` are equal to the roots of the `other`,
+    /// regardless of the counts.
+    pub fn roots_equal(&self, other: &Self) -> bool {
+        self.transactions_root == other.transactions_root
+            && self.message_receipt_root == other.message_receipt_root
+    }
+
+    /// Returns which fields differ between `self` and the `other`.
+    pub fn fields_diff(&self, other: &Self) -> ApplicationFieldsDiff {
+        ApplicationFieldsDiff {
+            transactions_count: self.transactions_count != other.transactions_count,
+            message_receipt_count: self.message_receipt_count
+                != other.message_receipt_count,
+            transactions_root: self.transactions_root != other.transactions_root,
+            message_receipt_root: self.message_receipt_root != other.message_receipt_root,
+        }
+    }
+}
+
+/// Calculate the hash of the [`HeaderVersion::V0`] application header
+/// from its raw fields, without creating the [`ApplicationHeader`].
+///
+/// The result is the same as [`ApplicationHeader::hash`] for these fields.
+/// With the `multi-da` feature, the header uses the default `da_chain`.
+pub fn application_hash(
+    da_height: DaBlockHeight,
+    transactions_count: u64,
+    message_receipt_count: u64,
+    transactions_root: &Bytes32,
+    message_receipt_root: &Bytes32,
+) -> Bytes32 {
+    ApplicationHeader {
+        version: HeaderVersion::V0,
+        da_height,
+        #[cfg(feature = "multi-da")]
+        da_chain: DaChainId::default(),
+        generated: GeneratedApplicationFields {
+            transactions_count,
+            message_receipt_count,
+            transactions_root: *transactions_root,
+            message_receipt_root: *message_receipt_root,
+        },
+    }
+    .hash()
+}
+
+impl<Generated> ApplicationHeader<Generated> {
+    /// Verifies that the `da_height` doesn't go back relative to the `parent`
+    /// and is at most `max_gap` ahead of it.
+    pub fn verify_da_progression(
+        &self,
+        parent: &ApplicationHeader<GeneratedApplicationFields>,
+        max_gap: u64,
+    ) -> Result<(), DaHeightError> {
+        let (parent, current) = (parent.da_height, self.da_height);
+        if current < parent {
+            return Err(DaHeightError::Decreasing { parent, current })
+        }
+        if current.0 - parent.0 > max_gap {
+            return Err(DaHeightError::GapTooLarge {
+                parent,
+                current,
+                max_gap,
+            })
+        }
+        Ok(())
+    }
 }
 
 impl ConsensusHeader<GeneratedConsensusFields> {
     /// Hash the consensus header.
+    ///
+    /// The result is the [`BlockId`] of the block. The preimage is, in order:
+    /// - `prev_root`;
+    /// - `height` as 4 big-endian bytes;
+    /// - `time` as the 8 big-endian bytes of the TAI64 label;
+    /// - `time_nanos` as 4 big-endian bytes, only with the `nanos` feature;
+    /// - `application_hash`.
+    ///
+    /// Without the `nanos` feature, the hash has the second resolution of the time.
     pub fn hash(&self) -> BlockId {
+        self.hash_with::<crate::fuel_crypto::Hasher>()
+    }
+
+    /// Hash the consensus header with the digest function of the `Hasher`.
+    ///
+    /// The preimage is the same as for the [`ConsensusHeader::hash`].
+    pub fn hash_with<Hasher: HeaderHasher>(&self) -> BlockId {
+        let mut hasher = Hasher::default();
+        self.hash_into(&mut hasher);
+        BlockId::from(hasher.finalize())
+    }
+
+    /// Feeds the preimage of the [`ConsensusHeader::hash`] into the `hasher`
+    /// without finalizing it, so the caller can compose it with other inputs.
+    ///
+    /// Finalizing the fresh hasher after this call gives the same result as
+    /// the [`ConsensusHeader::hash_with`].
+    pub fn hash_into<Hasher: HeaderHasher>(&self, hasher: &mut Hasher) {
+        self.input_preimage(|bytes| hasher.input(bytes));
+    }
+
+    /// The exact bytes hashed by the [`ConsensusHeader::hash`], to compare
+    /// the preimages when the hashes of different implementations don't match.
+    pub fn hash_preimage(&self) -> Vec<u8> {
+        let mut preimage = vec![];
+        self.input_preimage(|bytes| preimage.extend_from_slice(bytes));
+        preimage
+    }
+
+    fn input_preimage(&self, mut input: impl FnMut(&[u8])) {
         // Order matters and is the same as the spec.
-        let mut hasher = crate::fuel_crypto::Hasher::default();
-        hasher.input(self.prev_root.as_ref());
-        hasher.input(&self.height.to_bytes()[..]);
-        hasher.input(self.time.0.to_be_bytes());
-        hasher.input(self.application_hash.as_ref());
-        BlockId::from(hasher.digest())
+        input(self.prev_root.as_ref());
+        input(&self.height.to_bytes());
+        input(&hash_encode_u64_be(self.time.0));
+        #[cfg(feature = "nanos")]
+        input(&self.time_nanos.to_be_bytes());
+        input(self.application_hash.as_ref());
+    }
+}
+
+/// Calculate the [`BlockId`] from the raw fields of the consensus header,
+/// without creating the [`ConsensusHeader`].
+///
+/// The result is the same as [`ConsensusHeader::hash`] for these fields.
+/// With the `nanos` feature, the header has zero `time_nanos`.
+pub fn block_id(
+    prev_root: &Bytes32,
+    height: BlockHeight,
+    time: Tai64,
+    application_hash: &Bytes32,
+) -> BlockId {
+    ConsensusHeader {
+        prev_root: *prev_root,
+        height,
+        time,
+        #[cfg(feature = "nanos")]
+        time_nanos: 0,
+        generated: GeneratedConsensusFields {
+            application_hash: *application_hash,
+        },
+    }
+    .hash()
+}
+
+/// Encodes the integer field of the hash preimage.
+///
+/// All integers are hashed in the big-endian order, see the [`hashing_layout`].
+fn hash_encode_u64_be(value: u64) -> [u8; 8] {
+    value.to_be_bytes()
+}
+
+/// The digest function of the header hashes, see the [`ApplicationHeader::hash_with`]
+/// and the [`ConsensusHeader::hash_with`].
+///
+/// The preimage is the same for all hashers, only the digest function differs.
+pub trait HeaderHasher: Default {
+    /// Appends the `bytes` to the preimage.
+    fn input(&mut self, bytes: &[u8]);
+
+    /// Returns the digest of the preimage.
+    fn finalize(self) -> Bytes32;
+}
+
+/// The SHA-256 hasher that is used by the consensus.
+impl HeaderHasher for crate::fuel_crypto::Hasher {
+    fn input(&mut self, bytes: &[u8]) {
+        crate::fuel_crypto::Hasher::input(self, bytes)
+    }
+
+    fn finalize(self) -> Bytes32 {
+        crate::fuel_crypto::Hasher::finalize(self)
+    }
+}
+
+/// The Keccak-256 hasher for the interoperability with the EVM.
+///
+/// The hashes calculated with it are not accepted by the consensus.
+#[cfg(feature = "keccak-header")]
+#[derive(Clone, Debug, Default)]
+pub struct KeccakHeaderHasher(sha3::Keccak256);
+
+#[cfg(feature = "keccak-header")]
+impl HeaderHasher for KeccakHeaderHasher {
+    fn input(&mut self, bytes: &[u8]) {
+        sha3::Digest::update(&mut self.0, bytes)
+    }
+
+    fn finalize(self) -> Bytes32 {
+        Bytes32::new(sha3::Digest::finalize(self.0).into())
+    }
+}
+
+/// The byte order of an integer field in the hash preimage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endianness {
+    /// The most significant byte goes first.
+    Big,
+    /// The least significant byte goes first.
+    Little,
+}
+
+/// The description of one field of the hash preimage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HashedField {
+    /// The name of the field in the header.
+    pub name: &'static str,
+    /// The number of bytes of the field in the preimage.
+    pub len: usize,
+    /// The byte order of the integer field, or `None` for the hashes
+    /// that are hashed as they are.
+    pub endianness: Option<Endianness>,
+}
+
+/// The layout of the preimages of the header hashes, see the [`hashing_layout`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HashingLayout {
+    /// The preimage of the [`ApplicationHeader::hash`] of the [`HeaderVersion::V0`].
+    /// Other versions prefix it with the version byte.
+    pub application: &'static [HashedField],
+    /// The preimage of the [`ConsensusHeader::hash`].
+    pub consensus: &'static [HashedField],
+}
+
+/// Returns the layout of the preimages of the header hashes, in order,
+/// for the implementations of the header hashing outside of this crate.
+///
+/// The digest of the preimage is the SHA-256.
+pub fn hashing_layout() -> HashingLayout {
+    const fn integer(name: &'static str, len: usize) -> HashedField {
+        HashedField {
+            name,
+            len,
+            endianness: Some(Endianness::Big),
+        }
+    }
+    const fn hash(name: &'static str) -> HashedField {
+        HashedField {
+            name,
+            len: 32,
+            endianness: None,
+        }
+    }
+
+    const APPLICATION: &[HashedField] = &[
+        integer("da_height", 8),
+        #[cfg(feature = "multi-da")]
+        integer("da_chain", 8),
+        integer("transactions_count", 8),
+        integer("message_receipt_count", 8),
+        hash("transactions_root"),
+        hash("message_receipt_root"),
+    ];
+    const CONSENSUS: &[HashedField] = &[
+        hash("prev_root"),
+        integer("height", 4),
+        integer("time", 8),
+        #[cfg(feature = "nanos")]
+        integer("time_nanos", 4),
+        hash("application_hash"),
+    ];
+
+    HashingLayout {
+        application: APPLICATION,
+        consensus: CONSENSUS,
+    }
+}
+
+/// The labels starting from `2^63` are reserved by the TAI64 format.
+const MAX_TAI64: u64 = (1 << 63) - 1;
+
+/// The `time_nanos` are less than a second.
+#[cfg(feature = "nanos")]
+const NANOS_PER_SECOND: u32 = 1_000_000_000;
+
+/// Returns the `time` advanced by `secs` seconds, for example, to get the time
+/// of the next block from the block interval.
+///
+/// The result saturates at the largest valid TAI64 label, `2^63 - 1`,
+/// instead of overflowing into the reserved labels.
+pub fn advance_time(time: Tai64, secs: u64) -> Tai64 {
+    Tai64(time.0.saturating_add(secs).min(MAX_TAI64))
+}
+
+/// Returns the `headers` with heights in the inclusive `range`, in the order
+/// of the `headers`.
+///
+/// The reversed range, like `5..=3`, is empty and matches no headers.
+pub fn filter_by_height_range(
+    headers: &[BlockHeader],
+    range: core::ops::RangeInclusive<BlockHeight>,
+) -> Vec<&BlockHeader> {
+    headers
+        .iter()
+        .filter(|header| range.contains(header.height()))
+        .collect()
+}
+
+/// Indexes the `headers` by their heights, for example, for the range queries
+/// of explorers.
+///
+/// If several headers have the same height, the last of them in the `headers`
+/// is kept, like [`BTreeMap::insert`](std::collections::BTreeMap::insert) does;
+/// use the [`BlockHeader::is_sibling_of`] to detect forks before indexing.
+pub fn index_by_height(
+    headers: Vec<BlockHeader>,
+) -> std::collections::BTreeMap<BlockHeight, BlockHeader> {
+    headers
+        .into_iter()
+        .map(|header| (*header.height(), header))
+        .collect()
+}
+
+/// Accumulates the ids of the `headers` into the root of the binary merkle tree,
+/// the single commitment to the whole range of headers.
+///
+/// The ids are pushed in the order of the `headers`, so the root of the chain
+/// from the genesis block is the `prev_root` of the next block. The `headers`
+/// are not validated, use the [`validate_chain`] for that.
+pub fn accumulate_ids(headers: &[BlockHeader]) -> Bytes32 {
+    let mut tree = fuel_merkle::binary::in_memory::MerkleTree::new();
+    for header in headers {
+        tree.push(header.id().as_slice());
+    }
+    tree.root().into()
+}
+
+/// Validates that the `headers` form a chain: each header is internally consistent,
+/// each header after the first is the direct child of the previous one, and
+/// the `prev_root` of each header is the root of all blocks before it.
+///
+/// The `accumulator` must contain the ids of all blocks before the first header.
+/// If it is empty, the first header must have the zeroed `prev_root` of the genesis
+/// block. The height of the first header is not checked because its parent is not
+/// a part of the `headers`.
+///
+/// On success, the ids of the validated headers are pushed into the `accumulator`,
+/// so it can be reused to validate the next range of headers. On error,
+/// the `accumulator` is not modified.
+pub fn validate_chain(
+    headers: &[BlockHeader],
+    accumulator: &mut PrevRootBuilder,
+) -> Result<(), ChainValidationError> {
+    let mut validated = accumulator.clone();
+    for (index, header) in headers.iter().enumerate() {
+        header
+            .validate()
+            .map_err(|error| ChainValidationError::InvalidHeader { index, error })?;
+
+        if let Some(parent) = index.checked_sub(1).map(|i| &headers[i]) {
+            if parent.next_height() != Some(*header.height()) {
+                return Err(ChainValidationError::NonConsecutiveHeight {
+                    index,
+                    parent_height: *parent.height(),
+                    height: *header.height(),
+                })
+            }
+        }
+
+        // The `validated` already contains the ids of all blocks before the header.
+        let prev_root = if validated.count() == 0 {
+            Bytes32::zeroed()
+        } else {
+            validated.root()
+        };
+        if !ct_eq(prev_root, header.prev_root()) {
+            return Err(ChainValidationError::PrevRootMismatch { index })
+        }
+
+        validated.push(&header.id());
+    }
+    *accumulator = validated;
+    Ok(())
+}
+
+impl<Generated> ConsensusHeader<Generated> {
+    /// Verifies that the `time` doesn't go back relative to the `parent_time`
+    /// and is at most `max_drift_secs` ahead of it.
+    pub fn verify_time_progression(
+        &self,
+        parent_time: Tai64,
+        max_drift_secs: u64,
+    ) -> Result<(), TimeError> {
+        let (parent, current) = (parent_time, self.time);
+        if current < parent {
+            return Err(TimeError::NonMonotonic { parent, current })
+        }
+        if current.0 - parent.0 > max_drift_secs {
+            return Err(TimeError::ExcessiveDrift {
+                parent,
+                current,
+                max_drift_secs,
+            })
+        }
+        Ok(())
+    }
+
+    /// Verifies that the `prev_root` is the root after appending the `parent_id`
+    /// to the `accumulator` of all blocks before the parent.
+    pub fn verify_prev_root(
+        &self,
+        parent_id: &BlockId,
+        accumulator: &PrevRootBuilder,
+    ) -> bool {
+        ct_eq(accumulator.root_with(parent_id), self.prev_root)
+    }
+}
+
+/// Compares hashes in constant time.
+///
+/// The comparison of the expected and received hashes shouldn't stop at the first
+/// different byte. Otherwise, the time of the validation leaks how close
+/// a forged hash is to the expected one.
+fn ct_eq(a: impl AsRef<[u8]>, b: impl AsRef<[u8]>) -> bool {
+    use subtle::ConstantTimeEq;
+    a.as_ref().ct_eq(b.as_ref()).into()
+}
+
+/// Calculates the `prev_root` of the next block: the merkle root of ids of all previous blocks.
+/// Ids should be pushed in the order of the blocks starting from the genesis block.
+///
+/// The genesis block doesn't have previous blocks and uses the zeroed `prev_root`
+/// instead of the root of the empty tree.
+///
+/// The builder keeps only the roots of the perfect subtrees of the merkle tree,
+/// the peaks, so it can be saved as a checkpoint and resumed with
+/// the [`PrevRootBuilder::from_checkpoint`].
+#[derive(Clone, Debug, Default)]
+pub struct PrevRootBuilder {
+    /// The roots of the perfect subtrees, from the largest to the smallest.
+    /// The subtree of the height `h` is present if the bit `h` of the `count` is set.
+    peaks: Vec<Bytes32>,
+    count: u64,
+}
+
+impl PrevRootBuilder {
+    /// Creates the builder without blocks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resumes the builder from the checkpoint of `count` blocks, for example,
+    /// to sync a light client from the trusted block instead of the genesis block.
+    ///
+    /// The `accumulated_root` is the root of `count` blocks, i.e., the `prev_root`
+    /// of the block at the height `count`. The root alone is not enough to append
+    /// new blocks, so the checkpoint also contains the `peaks` returned by
+    /// the [`PrevRootBuilder::peaks`].
+    ///
+    /// Returns `None` if the number of `peaks` doesn't match the `count` or
+    /// the `peaks` don't produce the `accumulated_root`. The caller must get
+    /// the `accumulated_root` from a trusted source, because the `prev_root`s
+    /// calculated by the resumed builder are only as correct as the checkpoint.
+    pub fn from_checkpoint(
+        accumulated_root: Bytes32,
+        count: u64,
+        peaks: Vec<Bytes32>,
+    ) -> Option<Self> {
+        if peaks.len() != count.count_ones() as usize {
+            return None
+        }
+        let builder = Self { peaks, count };
+        ct_eq(builder.root(), accumulated_root).then_some(builder)
+    }
+
+    /// The number of pushed blocks.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The roots of the perfect subtrees of the pushed blocks, from the largest
+    /// to the smallest, to create the checkpoint for
+    /// the [`PrevRootBuilder::from_checkpoint`].
+    pub fn peaks(&self) -> &[Bytes32] {
+        &self.peaks
+    }
+
+    /// Appends the id of the next block.
+    pub fn push(&mut self, id: &BlockId) {
+        let mut node = merkle_leaf_sum(id.as_slice());
+        // Each trailing one of the `count` is the subtree of the same height
+        // as the `node`, so they are merged.
+        for _ in 0..self.count.trailing_ones() {
+            let left = self.peaks.pop().expect("The peak exists for each set bit");
+            node = merkle_node_sum(&left, &node);
+        }
+        self.peaks.push(node);
+        self.count += 1;
+    }
+
+    /// The `prev_root` of the block after all pushed blocks.
+    pub fn root(&self) -> Bytes32 {
+        let mut peaks = self.peaks.iter().rev();
+        match peaks.next() {
+            None => EMPTY_MERKLE_ROOT,
+            Some(last) => peaks.fold(*last, |root, peak| merkle_node_sum(peak, &root)),
+        }
     }
+
+    /// The `prev_root` of the block after all pushed blocks and the block with the `id`.
+    /// The builder is not modified.
+    pub fn root_with(&self, id: &BlockId) -> Bytes32 {
+        let mut builder = self.clone();
+        builder.push(id);
+        builder.root()
+    }
+}
+
+/// The hash of the leaf of the binary merkle tree, the same as in the `fuel_merkle`.
+fn merkle_leaf_sum(data: &[u8]) -> Bytes32 {
+    let mut hasher = crate::fuel_crypto::Hasher::default();
+    hasher.input([0x00]);
+    hasher.input(data);
+    hasher.digest()
+}
+
+/// The hash of the node of the binary merkle tree, the same as in the `fuel_merkle`.
+fn merkle_node_sum(left: &Bytes32, right: &Bytes32) -> Bytes32 {
+    let mut hasher = crate::fuel_crypto::Hasher::default();
+    hasher.input([0x01]);
+    hasher.input(left);
+    hasher.input(right);
+    hasher.digest()
 }
 
 #[cfg(any(test, feature = "test-helpers"))]
@@ -327,6 +2667,8 @@ where
     fn default() -> Self {
         Self {
             time: Tai64::UNIX_EPOCH,
+            #[cfg(feature = "nanos")]
+            time_nanos: 0,
             height: BlockHeight::default(),
             prev_root: Bytes32::default(),
             generated: Default::default(),
@@ -334,6 +2676,18 @@ where
     }
 }
 
+impl core::fmt::Display for BlockHeader {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "block#{} id={:#x}", **self.height(), self.id())
+    }
+}
+
+impl core::fmt::Display for PartialBlockHeader {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "block#{}", **self.height())
+    }
+}
+
 impl core::ops::Deref for BlockHeader {
     type Target = ApplicationHeader<GeneratedApplicationFields>;
 
@@ -0,0 +1,113 @@
+//! Implementations of the [`Arbitrary`] for the header types to fuzz
+//! the validation of headers.
+//!
+//! The generated [`BlockHeader`]s are consistent: the `application_hash` is
+//! calculated from the generated application header instead of being random,
+//! and the roots of the zero counts are the roots of the empty tree.
+//! For the same reason, there is no implementation for the
+//! `ConsensusHeader<GeneratedConsensusFields>`.
+
+use super::{
+    ApplicationHeader,
+    BlockHeader,
+    ConsensusHeader,
+    GeneratedApplicationFields,
+    HeaderVersion,
+    PartialBlockHeader,
+    EMPTY_MERKLE_ROOT,
+    MAX_MESSAGES_PER_BLOCK,
+    MAX_TAI64,
+};
+use crate::{
+    blockchain::primitives::{
+        DaBlockHeight,
+        Empty,
+    },
+    fuel_types::{
+        BlockHeight,
+        Bytes32,
+    },
+};
+use arbitrary::{
+    Arbitrary,
+    Result,
+    Unstructured,
+};
+use tai64::Tai64;
+
+impl<'a> Arbitrary<'a> for HeaderVersion {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(*u.choose(&[HeaderVersion::V0, HeaderVersion::V1])?)
+    }
+}
+
+impl<'a> Arbitrary<'a> for GeneratedApplicationFields {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            transactions_count: u.arbitrary()?,
+            message_receipt_count: u.int_in_range(0..=MAX_MESSAGES_PER_BLOCK)?,
+            transactions_root: Bytes32::new(u.arbitrary()?),
+            message_receipt_root: Bytes32::new(u.arbitrary()?),
+        })
+    }
+}
+
+impl<'a, Generated: Arbitrary<'a>> Arbitrary<'a> for ApplicationHeader<Generated> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            version: u.arbitrary()?,
+            da_height: DaBlockHeight(u.arbitrary()?),
+            #[cfg(feature = "multi-da")]
+            da_chain: crate::blockchain::primitives::DaChainId(u.arbitrary()?),
+            generated: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for ConsensusHeader<Empty> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            prev_root: Bytes32::new(u.arbitrary()?),
+            height: BlockHeight::from(u32::arbitrary(u)?),
+            time: Tai64(u.int_in_range(0..=MAX_TAI64)?),
+            #[cfg(feature = "nanos")]
+            time_nanos: u.int_in_range(0..=999_999_999)?,
+            generated: Empty {},
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Empty {
+    fn arbitrary(_: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Empty {})
+    }
+}
+
+impl<'a> Arbitrary<'a> for PartialBlockHeader {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            application: u.arbitrary()?,
+            consensus: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for BlockHeader {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let partial = PartialBlockHeader::arbitrary(u)?;
+        let GeneratedApplicationFields {
+            transactions_count,
+            message_receipt_count,
+            transactions_root,
+            message_receipt_root,
+        } = u.arbitrary()?;
+        let empty_if_zero =
+            |count, root| if count == 0 { EMPTY_MERKLE_ROOT } else { root };
+        Ok(partial.generate_with_roots(
+            transactions_count,
+            message_receipt_count,
+            empty_if_zero(transactions_count, transactions_root),
+            empty_if_zero(message_receipt_count, message_receipt_root),
+        ))
+    }
+}
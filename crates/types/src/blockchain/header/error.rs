@@ -0,0 +1,212 @@
+//! The errors of the header types.
+//!
+//! The errors are re-exported from the [`header`](super) module, so they can be
+//! imported from either of the modules.
+//!
+//! The errors always implement the `std::error::Error` via the `thiserror`.
+//! There is no `no_std` fallback, because the header types require `std`.
+
+use super::CANONICAL_HEADER_SIZE;
+use crate::{
+    blockchain::primitives::{
+        BlockId,
+        DaBlockHeight,
+    },
+    fuel_types::{
+        BlockHeight,
+        Bytes32,
+        MessageId,
+    },
+};
+use tai64::Tai64;
+
+/// The error returned when the [`BlockHeader`](super::BlockHeader) is not
+/// internally consistent.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HeaderValidationError {
+    /// The `application_hash` of the consensus header doesn't match
+    /// the hash of the application header.
+    #[error("The application hash {actual:#x} doesn't match the hash of the application header {expected:#x}")]
+    ApplicationHashMismatch {
+        /// The hash of the application header.
+        expected: Bytes32,
+        /// The `application_hash` stored in the consensus header.
+        actual: Bytes32,
+    },
+    /// The cached id doesn't match the hash of the header.
+    #[error("The cached block id {cached:#x} doesn't match the hash of the header {actual:#x}")]
+    StaleMetadata {
+        /// The id stored in the metadata.
+        cached: BlockId,
+        /// The hash of the header.
+        actual: BlockId,
+    },
+    /// The header at zero height doesn't follow the rules of the genesis block.
+    #[error("The genesis header should have zero DA height and zeroed previous root, but got {da_height} and {prev_root:#x}")]
+    GenesisConstraintViolated {
+        /// The DA height of the header.
+        da_height: DaBlockHeight,
+        /// The previous root of the header.
+        prev_root: Bytes32,
+    },
+    /// The count of the application header is zero, but the corresponding root
+    /// is not the root of the empty tree.
+    #[error("The `{field}` should be the root of the empty tree for zero count, but got {root:#x}")]
+    CountRootMismatch {
+        /// The name of the root field.
+        field: &'static str,
+        /// The root stored in the application header.
+        root: Bytes32,
+    },
+    /// The `message_receipt_count` exceeds the
+    /// [`MAX_MESSAGES_PER_BLOCK`](super::MAX_MESSAGES_PER_BLOCK).
+    #[error("Too many message receipts: {count}, the maximum is {max}")]
+    TooManyMessages {
+        /// The `message_receipt_count` of the header.
+        count: u64,
+        /// The maximum number of messages.
+        max: u64,
+    },
+}
+
+/// The error returned by [`validate_chain`](super::validate_chain) for the first invalid header.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChainValidationError {
+    /// The header is not internally consistent.
+    #[error("The header at index {index} is invalid: {error}")]
+    InvalidHeader {
+        /// The index of the header.
+        index: usize,
+        /// The reason why the header is invalid.
+        #[source]
+        error: HeaderValidationError,
+    },
+    /// The height of the header doesn't follow the height of the previous header.
+    #[error("The header at index {index} has height {height}, but the previous one has height {parent_height}")]
+    NonConsecutiveHeight {
+        /// The index of the header.
+        index: usize,
+        /// The height of the previous header.
+        parent_height: BlockHeight,
+        /// The height of the header.
+        height: BlockHeight,
+    },
+    /// The `prev_root` of the header is not the root of the previous blocks.
+    #[error("The header at index {index} has an unexpected previous root")]
+    PrevRootMismatch {
+        /// The index of the header.
+        index: usize,
+    },
+}
+
+impl ChainValidationError {
+    /// The index of the first invalid header.
+    pub fn index(&self) -> usize {
+        match self {
+            Self::InvalidHeader { index, .. }
+            | Self::NonConsecutiveHeight { index, .. }
+            | Self::PrevRootMismatch { index } => *index,
+        }
+    }
+}
+
+/// The error returned when the `da_height` of the header doesn't follow
+/// the `da_height` of its parent.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DaHeightError {
+    /// The DA height is lower than the DA height of the parent.
+    #[error(
+        "The DA height {current} is lower than the DA height of the parent {parent}"
+    )]
+    Decreasing {
+        /// The DA height of the parent.
+        parent: DaBlockHeight,
+        /// The DA height of the header.
+        current: DaBlockHeight,
+    },
+    /// The DA height is too far ahead of the DA height of the parent.
+    #[error("The DA height {current} is more than {max_gap} ahead of the DA height of the parent {parent}")]
+    GapTooLarge {
+        /// The DA height of the parent.
+        parent: DaBlockHeight,
+        /// The DA height of the header.
+        current: DaBlockHeight,
+        /// The maximum allowed difference between the DA heights.
+        max_gap: u64,
+    },
+}
+
+/// The error returned when the `time` of the header doesn't follow
+/// the `time` of its parent.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TimeError {
+    /// The time is earlier than the time of the parent.
+    #[error("The time {current:?} is earlier than the time of the parent {parent:?}")]
+    NonMonotonic {
+        /// The time of the parent.
+        parent: Tai64,
+        /// The time of the header.
+        current: Tai64,
+    },
+    /// The time is too far ahead of the time of the parent.
+    #[error("The time {current:?} is more than {max_drift_secs} seconds ahead of the time of the parent {parent:?}")]
+    ExcessiveDrift {
+        /// The time of the parent.
+        parent: Tai64,
+        /// The time of the header.
+        current: Tai64,
+        /// The maximum allowed difference between the times in seconds.
+        max_drift_secs: u64,
+    },
+    /// The time is too far in the future relative to the current time.
+    #[error("The time {time:?} is more than {max_future_secs} seconds ahead of the current time {now:?}")]
+    TooFarInFuture {
+        /// The current time.
+        now: Tai64,
+        /// The time of the header.
+        time: Tai64,
+        /// The maximum allowed difference between the times in seconds.
+        max_future_secs: u64,
+    },
+}
+
+/// The error returned when the [`BlockHeader`](super::BlockHeader) can't be generated
+/// from the [`PartialBlockHeader`](super::PartialBlockHeader).
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GenerateError {
+    /// The same message id is passed more than once.
+    #[error("The message id {0:#x} is duplicated")]
+    DuplicateMessageId(MessageId),
+    /// The number of transactions doesn't fit into `u64`.
+    #[error("Too many transactions: {0}")]
+    TooManyTransactions(usize),
+    /// The number of messages exceeds the
+    /// [`MAX_MESSAGES_PER_BLOCK`](super::MAX_MESSAGES_PER_BLOCK).
+    #[error("Too many messages: {0}")]
+    TooManyMessages(usize),
+}
+
+/// The error returned when the bytes are not a valid canonical encoding of
+/// the [`BlockHeader`](super::BlockHeader).
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CanonicalBytesError {
+    /// The length of the bytes is not equal to [`CANONICAL_HEADER_SIZE`].
+    #[error("The canonical header should be {CANONICAL_HEADER_SIZE} bytes, but got {0}")]
+    InvalidLength(usize),
+    /// The version byte doesn't match any [`HeaderVersion`](super::HeaderVersion).
+    #[error("Unknown header version: {0}")]
+    UnknownVersion(u8),
+    /// The nanoseconds of the time are not less than a second.
+    #[cfg(feature = "nanos")]
+    #[error("The time nanoseconds should be less than a second, but got {0}")]
+    InvalidTimeNanos(u32),
+    /// The decoded header is not internally consistent.
+    #[error(transparent)]
+    InvalidHeader(#[from] HeaderValidationError),
+}
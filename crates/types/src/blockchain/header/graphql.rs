@@ -0,0 +1,106 @@
+//! The conversion of the block header returned by the GraphQL API of the node
+//! into the [`BlockHeader`].
+//!
+//! The API returns hashes as hex strings and integers as decimal strings.
+//! It doesn't return the version of the application header, so the header
+//! is rebuilt with the [`HeaderVersion::V0`]. With the `multi-da` feature, the header
+//! uses the default `da_chain` as well, and with the `nanos` feature, zero
+//! `time_nanos`.
+
+use super::{
+    BlockHeader,
+    BlockHeaderRow,
+    HeaderValidationError,
+    HeaderVersion,
+};
+use core::str::FromStr;
+use tai64::Tai64;
+
+/// The block header in the format of the GraphQL API.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct GraphQlBlockHeader {
+    /// The hex id of the block.
+    pub id: String,
+    /// The decimal layer 1 height.
+    pub da_height: String,
+    /// The decimal number of transactions in this block.
+    pub transactions_count: String,
+    /// The decimal number of message receipts in this block.
+    pub message_receipt_count: String,
+    /// The hex merkle root of transactions.
+    pub transactions_root: String,
+    /// The hex merkle root of message receipts in this block.
+    pub message_receipt_root: String,
+    /// The decimal fuel block height.
+    pub height: String,
+    /// The hex merkle root of all previous block header hashes.
+    pub prev_root: String,
+    /// The decimal TAI64 label of the block producer time.
+    pub time: String,
+    /// The hex hash of the application header.
+    pub application_hash: String,
+}
+
+/// The error returned when the [`GraphQlBlockHeader`] can't be converted
+/// into the [`BlockHeader`].
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum GraphQlHeaderError {
+    /// The field is not a valid hex hash or decimal integer.
+    #[error("The field `{field}` has the malformed value `{value}`")]
+    MalformedField {
+        /// The name of the field.
+        field: &'static str,
+        /// The value of the field.
+        value: String,
+    },
+    /// The parsed header is not internally consistent.
+    #[error(transparent)]
+    InvalidHeader(#[from] HeaderValidationError),
+}
+
+impl TryFrom<GraphQlBlockHeader> for BlockHeader {
+    type Error = GraphQlHeaderError;
+
+    /// Parses the fields and validates the `application_hash` and the `id`.
+    fn try_from(header: GraphQlBlockHeader) -> Result<Self, Self::Error> {
+        fn parse<T: FromStr>(
+            field: &'static str,
+            value: &str,
+        ) -> Result<T, GraphQlHeaderError> {
+            value
+                .parse()
+                .map_err(|_| GraphQlHeaderError::MalformedField {
+                    field,
+                    value: value.to_string(),
+                })
+        }
+
+        let row = BlockHeaderRow {
+            version: HeaderVersion::V0,
+            da_height: parse::<u64>("daHeight", &header.da_height)?.into(),
+            #[cfg(feature = "multi-da")]
+            da_chain: Default::default(),
+            transactions_count: parse("transactionsCount", &header.transactions_count)?,
+            message_receipt_count: parse(
+                "messageReceiptCount",
+                &header.message_receipt_count,
+            )?,
+            transactions_root: parse("transactionsRoot", &header.transactions_root)?,
+            message_receipt_root: parse(
+                "messageReceiptRoot",
+                &header.message_receipt_root,
+            )?,
+            prev_root: parse("prevRoot", &header.prev_root)?,
+            height: parse::<u32>("height", &header.height)?.into(),
+            time: Tai64(parse("time", &header.time)?),
+            #[cfg(feature = "nanos")]
+            time_nanos: 0,
+            application_hash: parse("applicationHash", &header.application_hash)?,
+            id: parse("id", &header.id)?,
+        };
+        Ok(row.try_into()?)
+    }
+}
@@ -0,0 +1,218 @@
+//! The protobuf representation of the block header for the cross-language tooling.
+//!
+//! The message is declared with the `prost` derive instead of a `.proto` file,
+//! so it doesn't require the `protoc` at build time. The equivalent schema is:
+//!
+//! ```proto
+//! message HeaderProto {
+//!     uint32 version = 1;
+//!     uint64 da_height = 2;
+//!     uint64 transactions_count = 3;
+//!     uint64 message_receipt_count = 4;
+//!     bytes transactions_root = 5;
+//!     bytes message_receipt_root = 6;
+//!     bytes prev_root = 7;
+//!     uint32 height = 8;
+//!     uint64 time = 9;
+//!     bytes application_hash = 10;
+//!     uint64 da_chain = 11;
+//!     uint32 time_nanos = 12;
+//! }
+//! ```
+//!
+//! The `da_chain` and the `time_nanos` are always a part of the message, but they
+//! must be zero unless the `multi-da` and the `nanos` features are enabled.
+
+use super::{
+    ApplicationHeader,
+    BlockHeader,
+    BlockHeaderMetadata,
+    CanonicalBytesError,
+    ConsensusHeader,
+    GeneratedApplicationFields,
+    GeneratedConsensusFields,
+    HeaderValidationError,
+    HeaderVersion,
+};
+use crate::fuel_types::Bytes32;
+use tai64::Tai64;
+
+/// The block header as the protobuf message.
+#[derive(Clone, PartialEq, Eq, prost::Message)]
+pub struct HeaderProto {
+    /// The version of the application header.
+    #[prost(uint32, tag = "1")]
+    pub version: u32,
+    /// The layer 1 height of messages and events to include.
+    #[prost(uint64, tag = "2")]
+    pub da_height: u64,
+    /// Number of transactions in this block.
+    #[prost(uint64, tag = "3")]
+    pub transactions_count: u64,
+    /// Number of message receipts in this block.
+    #[prost(uint64, tag = "4")]
+    pub message_receipt_count: u64,
+    /// The 32-byte merkle root of transactions.
+    #[prost(bytes = "vec", tag = "5")]
+    pub transactions_root: Vec<u8>,
+    /// The 32-byte merkle root of message receipts in this block.
+    #[prost(bytes = "vec", tag = "6")]
+    pub message_receipt_root: Vec<u8>,
+    /// The 32-byte merkle root of all previous block header hashes.
+    #[prost(bytes = "vec", tag = "7")]
+    pub prev_root: Vec<u8>,
+    /// Fuel block height.
+    #[prost(uint32, tag = "8")]
+    pub height: u32,
+    /// The block producer time as the TAI64 label.
+    #[prost(uint64, tag = "9")]
+    pub time: u64,
+    /// The 32-byte hash of the application header.
+    #[prost(bytes = "vec", tag = "10")]
+    pub application_hash: Vec<u8>,
+    /// The layer 1 chain of the `da_height`, zero without the `multi-da` feature.
+    #[prost(uint64, tag = "11")]
+    pub da_chain: u64,
+    /// The nanoseconds of the block producer time, zero without the `nanos` feature.
+    #[prost(uint32, tag = "12")]
+    pub time_nanos: u32,
+}
+
+/// The error returned when the [`HeaderProto`] can't be converted
+/// into the [`BlockHeader`].
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ProtoHeaderError {
+    /// The `bytes` field is not 32 bytes long.
+    #[error("The field `{field}` should be 32 bytes, but got {len}")]
+    InvalidLength {
+        /// The name of the field.
+        field: &'static str,
+        /// The length of the field.
+        len: usize,
+    },
+    /// The integer field doesn't fit into the type of the header field,
+    /// or it is not zero while the feature that uses it is disabled.
+    #[error("The field `{field}` has the unsupported value {value}")]
+    UnsupportedValue {
+        /// The name of the field.
+        field: &'static str,
+        /// The value of the field.
+        value: u64,
+    },
+    /// The version or the nanoseconds are not valid.
+    #[error(transparent)]
+    InvalidField(#[from] CanonicalBytesError),
+    /// The decoded header is not internally consistent.
+    #[error(transparent)]
+    InvalidHeader(#[from] HeaderValidationError),
+}
+
+impl From<&BlockHeader> for HeaderProto {
+    fn from(header: &BlockHeader) -> Self {
+        Self {
+            version: header.application.version as u32,
+            da_height: header.application.da_height.0,
+            transactions_count: header.transactions_count,
+            message_receipt_count: header.message_receipt_count,
+            transactions_root: header.transactions_root.to_vec(),
+            message_receipt_root: header.message_receipt_root.to_vec(),
+            prev_root: header.consensus.prev_root.to_vec(),
+            height: *header.consensus.height,
+            time: header.consensus.time.0,
+            application_hash: header.consensus.application_hash.to_vec(),
+            #[cfg(feature = "multi-da")]
+            da_chain: header.application.da_chain.0,
+            #[cfg(not(feature = "multi-da"))]
+            da_chain: 0,
+            #[cfg(feature = "nanos")]
+            time_nanos: header.consensus.time_nanos,
+            #[cfg(not(feature = "nanos"))]
+            time_nanos: 0,
+        }
+    }
+}
+
+impl TryFrom<HeaderProto> for BlockHeader {
+    type Error = ProtoHeaderError;
+
+    /// Checks the lengths of the fields, validates the header, and populates
+    /// the metadata.
+    fn try_from(proto: HeaderProto) -> Result<Self, Self::Error> {
+        fn bytes32(
+            field: &'static str,
+            bytes: &[u8],
+        ) -> Result<Bytes32, ProtoHeaderError> {
+            let bytes: [u8; 32] =
+                bytes
+                    .try_into()
+                    .map_err(|_| ProtoHeaderError::InvalidLength {
+                        field,
+                        len: bytes.len(),
+                    })?;
+            Ok(bytes.into())
+        }
+        fn byte(field: &'static str, value: u32) -> Result<u8, ProtoHeaderError> {
+            u8::try_from(value).map_err(|_| ProtoHeaderError::UnsupportedValue {
+                field,
+                value: value.into(),
+            })
+        }
+
+        #[cfg(not(feature = "multi-da"))]
+        if proto.da_chain != 0 {
+            return Err(ProtoHeaderError::UnsupportedValue {
+                field: "da_chain",
+                value: proto.da_chain,
+            })
+        }
+        #[cfg(not(feature = "nanos"))]
+        if proto.time_nanos != 0 {
+            return Err(ProtoHeaderError::UnsupportedValue {
+                field: "time_nanos",
+                value: proto.time_nanos.into(),
+            })
+        }
+
+        let version: HeaderVersion = byte("version", proto.version)?.try_into()?;
+        let mut header = BlockHeader {
+            application: ApplicationHeader {
+                version,
+                da_height: proto.da_height.into(),
+                #[cfg(feature = "multi-da")]
+                da_chain: crate::blockchain::primitives::DaChainId(proto.da_chain),
+                generated: GeneratedApplicationFields {
+                    transactions_count: proto.transactions_count,
+                    message_receipt_count: proto.message_receipt_count,
+                    transactions_root: bytes32(
+                        "transactions_root",
+                        &proto.transactions_root,
+                    )?,
+                    message_receipt_root: bytes32(
+                        "message_receipt_root",
+                        &proto.message_receipt_root,
+                    )?,
+                },
+            },
+            consensus: ConsensusHeader {
+                prev_root: bytes32("prev_root", &proto.prev_root)?,
+                height: proto.height.into(),
+                time: Tai64(proto.time),
+                #[cfg(feature = "nanos")]
+                time_nanos: super::time_nanos_from_bytes(proto.time_nanos.to_be_bytes())?,
+                generated: GeneratedConsensusFields {
+                    application_hash: bytes32(
+                        "application_hash",
+                        &proto.application_hash,
+                    )?,
+                },
+            },
+            metadata: None,
+        };
+        header.validate()?;
+        header.metadata = Some(BlockHeaderMetadata {
+            id: header.consensus.hash(),
+        });
+        Ok(header)
+    }
+}
@@ -0,0 +1,77 @@
+//! Serde adapter that persists the [`BlockHeaderMetadata`] of the [`BlockHeader`]
+//! as the `Option<BlockId>`, enabled by the `serialize-metadata` feature.
+//!
+//! The deserialized id is compared with the id calculated from the fields,
+//! and the header with the stale id is rejected, so the [`BlockHeader::id`]
+//! of the deserialized header is always correct.
+//!
+//! The feature appends the id to the serialized header, so it changes
+//! the layout of the positional formats, like the `postcard`, for every crate
+//! in the build, see the [module](super) documentation.
+
+use super::{
+    ApplicationHeader,
+    BlockHeader,
+    BlockHeaderMetadata,
+    ConsensusHeader,
+    GeneratedApplicationFields,
+    GeneratedConsensusFields,
+    HeaderValidationError,
+};
+use crate::blockchain::primitives::BlockId;
+use serde::{
+    Deserialize,
+    Serialize,
+    Serializer,
+};
+
+/// Serializes the cached id, if any.
+pub fn serialize<S>(
+    metadata: &Option<BlockHeaderMetadata>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    metadata
+        .as_ref()
+        .map(BlockHeaderMetadata::id)
+        .serialize(serializer)
+}
+
+/// The serialized [`BlockHeader`] before the verification of the cached id.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockHeaderWithId {
+    application: ApplicationHeader<GeneratedApplicationFields>,
+    consensus: ConsensusHeader<GeneratedConsensusFields>,
+    #[serde(default)]
+    metadata: Option<BlockId>,
+}
+
+impl TryFrom<BlockHeaderWithId> for BlockHeader {
+    type Error = HeaderValidationError;
+
+    fn try_from(header: BlockHeaderWithId) -> Result<Self, Self::Error> {
+        let BlockHeaderWithId {
+            application,
+            consensus,
+            metadata,
+        } = header;
+        let metadata = match metadata {
+            Some(cached) => {
+                let actual = consensus.hash();
+                if !super::ct_eq(cached, actual) {
+                    return Err(HeaderValidationError::StaleMetadata { cached, actual })
+                }
+                Some(BlockHeaderMetadata::new(cached))
+            }
+            None => None,
+        };
+        Ok(BlockHeader {
+            application,
+            consensus,
+            metadata,
+        })
+    }
+}
@@ -0,0 +1,169 @@
+//! Serde adapter for the [`Tai64`] time that uses the RFC 3339 representation,
+//! like `2023-05-01T12:00:00Z`, for human-readable formats.
+//!
+//! Binary formats keep the default representation of the [`Tai64`].
+//! The adapter is applied to a field with
+//! `#[serde(with = "fuel_core_types::blockchain::header::serde_rfc3339")]`.
+//!
+//! Only the years from `0000` to `9999` are supported. The time is always
+//! serialized in UTC, while the deserialization accepts any offset.
+//! Fractional seconds are not supported because the [`Tai64`] has
+//! the precision of seconds.
+
+use serde::{
+    de::Error as _,
+    ser::Error as _,
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer,
+};
+use tai64::Tai64;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+const MAX_YEAR: i64 = 9999;
+
+/// Serializes the `time` as an RFC 3339 string for human-readable formats.
+pub fn serialize<S>(time: &Tai64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        let formatted = format(*time)
+            .ok_or_else(|| S::Error::custom("The time is out of the RFC 3339 range"))?;
+        serializer.serialize_str(&formatted)
+    } else {
+        time.serialize(serializer)
+    }
+}
+
+/// Deserializes the time from an RFC 3339 string for human-readable formats.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Tai64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        let formatted = String::deserialize(deserializer)?;
+        parse(&formatted).map_err(D::Error::custom)
+    } else {
+        Tai64::deserialize(deserializer)
+    }
+}
+
+fn format(time: Tai64) -> Option<String> {
+    let secs = time.to_unix();
+    let (year, month, day) = civil_from_days(secs.div_euclid(SECONDS_PER_DAY));
+    if !(0..=MAX_YEAR).contains(&year) {
+        return None
+    }
+    let secs_of_day = secs.rem_euclid(SECONDS_PER_DAY);
+    let (hour, minute, second) = (
+        secs_of_day / 3600,
+        secs_of_day % 3600 / 60,
+        secs_of_day % 60,
+    );
+    Some(format!(
+        "{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}Z"
+    ))
+}
+
+fn parse(formatted: &str) -> Result<Tai64, &'static str> {
+    const INVALID: &str = "Expected an RFC 3339 time like `2023-05-01T12:00:00Z`";
+
+    let bytes = formatted.as_bytes();
+    if bytes.len() < 20
+        || bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || !matches!(bytes[10], b'T' | b't')
+        || bytes[13] != b':'
+        || bytes[16] != b':'
+    {
+        return Err(INVALID)
+    }
+    let field = |range: core::ops::Range<usize>| digits(&bytes[range]).ok_or(INVALID);
+    let (year, month, day) = (field(0..4)?, field(5..7)?, field(8..10)?);
+    let (hour, minute, second) = (field(11..13)?, field(14..16)?, field(17..19)?);
+
+    let offset = match &bytes[19..] {
+        [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), _, _, b':', _, _] => {
+            let (hours, minutes) = (field(20..22)?, field(23..25)?);
+            if hours > 23 || minutes > 59 {
+                return Err(INVALID)
+            }
+            let offset = hours * 3600 + minutes * 60;
+            if *sign == b'-' {
+                -offset
+            } else {
+                offset
+            }
+        }
+        [b'.', ..] => return Err("Fractional seconds are not supported"),
+        _ => return Err(INVALID),
+    };
+
+    if !(1..=12).contains(&month)
+        || day < 1
+        || day > days_in_month(year, month)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return Err("The date or time is out of range")
+    }
+
+    let secs = days_from_civil(year, month, day) * SECONDS_PER_DAY
+        + hour * 3600
+        + minute * 60
+        + second
+        - offset;
+    Ok(Tai64::from_unix(secs))
+}
+
+fn digits(bytes: &[u8]) -> Option<i64> {
+    bytes.iter().try_fold(0i64, |acc, byte| {
+        byte.is_ascii_digit()
+            .then(|| acc * 10 + i64::from(byte - b'0'))
+    })
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Converts the number of days since the Unix epoch into the proleptic
+/// Gregorian `(year, month, day)`.
+///
+/// The algorithm is described in <http://howardhinnant.github.io/date_algorithms.html>.
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// The inverse of the [`civil_from_days`].
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
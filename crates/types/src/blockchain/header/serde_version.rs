@@ -0,0 +1,55 @@
+//! Serde adapter for the [`HeaderVersion`] of the [`ApplicationHeader`](super::ApplicationHeader).
+//!
+//! The headers stored before the versioning don't contain the version, so the
+//! [`HeaderVersion::V0`] is never serialized, and the missing version is decoded
+//! as the [`HeaderVersion::V0`].
+//!
+//! Human-readable formats, like the JSON, serialize other versions as the field.
+//! Binary formats are positional, like the `postcard` of the database and the P2P,
+//! and have no place for the version without breaking the stored headers, so
+//! serializing another version returns an error instead of silently decoding it
+//! as the [`HeaderVersion::V0`] with the mismatching application hash.
+//! Such headers are encoded with the [`BlockHeaderRow`](super::BlockHeaderRow),
+//! the canonical bytes or the protobuf.
+
+use super::HeaderVersion;
+use serde::{
+    ser::Error as _,
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer,
+};
+
+/// Returns `true` if the `version` is skipped during the serialization.
+pub fn is_v0(version: &HeaderVersion) -> bool {
+    *version == HeaderVersion::V0
+}
+
+/// Serializes the version for human-readable formats, fails for binary formats.
+pub fn serialize<S>(version: &HeaderVersion, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        version.serialize(serializer)
+    } else {
+        Err(S::Error::custom(
+            "The binary serde layout of the header supports only the `V0` version",
+        ))
+    }
+}
+
+/// Deserializes the version for human-readable formats, binary formats
+/// don't contain it.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<HeaderVersion, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    if deserializer.is_human_readable() {
+        HeaderVersion::deserialize(deserializer)
+    } else {
+        <()>::deserialize(deserializer)?;
+        Ok(HeaderVersion::V0)
+    }
+}
@@ -0,0 +1,37 @@
+//! Helpers to fabricate headers in the tests of the dependent crates.
+
+use super::{
+    advance_time,
+    BlockHeader,
+    PartialBlockHeaderBuilder,
+    PrevRootBuilder,
+};
+use crate::fuel_types::Bytes32;
+use tai64::Tai64;
+
+/// Creates the chain of `n` empty headers starting from the genesis block.
+///
+/// The headers have consecutive heights, linked `prev_root`s, and times
+/// increasing by one second from the [`Tai64::UNIX_EPOCH`], so the chain passes
+/// the [`super::validate_chain`].
+pub fn chain_of(n: usize) -> Vec<BlockHeader> {
+    let mut accumulator = PrevRootBuilder::new();
+    let mut headers: Vec<BlockHeader> = Vec::with_capacity(n);
+    for height in 0..n as u32 {
+        let prev_root = match headers.last() {
+            None => Bytes32::zeroed(),
+            Some(parent) => {
+                accumulator.push(&parent.id());
+                accumulator.root()
+            }
+        };
+        let header = PartialBlockHeaderBuilder::new()
+            .prev_root(prev_root)
+            .height(height.into())
+            .time(advance_time(Tai64::UNIX_EPOCH, u64::from(height)))
+            .build()
+            .generate(&[], &[]);
+        headers.push(header);
+    }
+    headers
+}
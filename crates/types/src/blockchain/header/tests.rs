@@ -0,0 +1,2735 @@
+use super::{
+    test_helpers::chain_of,
+    *,
+};
+use core::str::FromStr;
+use postcard as _;
+use serde_json as _;
+
+// Headers are shared between threads behind `Arc`, so a field that isn't
+// `Send + Sync`, like a non-atomic cache of the metadata, must fail the compilation.
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<BlockHeader>();
+    assert_send_sync::<PartialBlockHeader>();
+    assert_send_sync::<BlockHeaderMetadata>();
+    assert_send_sync::<HeaderSummary>();
+    assert_send_sync::<SignedBlockHeader>();
+    assert_send_sync::<ValidatedBlockHeader>();
+    assert_send_sync::<BlockHeaderRow>();
+    assert_send_sync::<BlockHeaderView<'static>>();
+    assert_send_sync::<CowBlockHeader<'static>>();
+    assert_send_sync::<ArcBlockHeader>();
+    assert_send_sync::<RootPool>();
+};
+
+fn transactions(count: u64) -> Vec<Transaction> {
+    (0..count)
+        .map(|i| {
+            Transaction::script(
+                0,
+                i,
+                Default::default(),
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+                vec![],
+            )
+            .into()
+        })
+        .collect()
+}
+
+fn message_ids(count: u64) -> Vec<MessageId> {
+    (0..count)
+        .map(|i| {
+            let mut id = [0u8; 32];
+            id[..8].copy_from_slice(&i.to_be_bytes());
+            id.into()
+        })
+        .collect()
+}
+
+#[test]
+fn eq_ignores_cached_metadata() {
+    let header = BlockHeader::default();
+    let mut other = header.clone();
+
+    other.metadata = Some(BlockHeaderMetadata {
+        id: BlockId::from([1u8; 32]),
+    });
+    assert_eq!(header, other);
+
+    other.metadata = None;
+    assert_eq!(header, other);
+    assert_eq!(header.hash(), other.hash());
+}
+
+#[test]
+fn eq_detects_changes_of_generated_fields() {
+    let header = BlockHeader::default();
+    let mutations: [fn(&mut BlockHeader); 5] = [
+        |h| h.application.generated.transactions_count = 1,
+        |h| h.application.generated.message_receipt_count = 1,
+        |h| h.application.generated.transactions_root = [1u8; 32].into(),
+        |h| h.application.generated.message_receipt_root = [1u8; 32].into(),
+        |h| h.consensus.generated.application_hash = [1u8; 32].into(),
+    ];
+
+    for mutate in mutations {
+        let mut other = header.clone();
+        mutate(&mut other);
+        assert_ne!(header, other);
+    }
+}
+
+#[test]
+fn hash_map_treats_cached_and_not_cached_headers_as_same_key() {
+    let cached = BlockHeader::new_block(1u32.into(), Tai64(2));
+    let mut not_cached = cached.clone();
+    not_cached.metadata = None;
+
+    let mut map = std::collections::HashMap::new();
+    map.insert(cached, 1);
+    map.insert(not_cached, 2);
+
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.into_values().next(), Some(2));
+}
+
+#[test]
+fn validate_accepts_consistent_header() {
+    let mut header = PartialBlockHeaderBuilder::new()
+        .height(1u32.into())
+        .time(Tai64(2))
+        .build()
+        .generate(&[], &[]);
+    assert_eq!(header.validate(), Ok(()));
+
+    header.metadata = None;
+    assert_eq!(header.validate(), Ok(()));
+}
+
+#[test]
+fn validate_detects_application_hash_mismatch() {
+    let mut header = non_empty_header();
+    header.application.generated.transactions_count = 1;
+
+    assert!(matches!(
+        header.validate(),
+        Err(HeaderValidationError::ApplicationHashMismatch { .. })
+    ));
+}
+
+#[test]
+fn validate_detects_stale_metadata() {
+    let header = BlockHeader {
+        metadata: Some(BlockHeaderMetadata {
+            id: BlockId::from([1u8; 32]),
+        }),
+        ..PartialBlockHeader::default().generate(&[], &[])
+    };
+
+    assert_eq!(
+        header.validate(),
+        Err(HeaderValidationError::StaleMetadata {
+            cached: BlockId::from([1u8; 32]),
+            actual: header.hash(),
+        })
+    );
+}
+
+#[test]
+fn validate_detects_count_root_mismatch() {
+    let header = |transactions_count, transactions_root, message_receipt_root| {
+        PartialBlockHeader::default().generate_with_roots(
+            transactions_count,
+            0,
+            transactions_root,
+            message_receipt_root,
+        )
+    };
+    let root = Bytes32::from([1u8; 32]);
+
+    assert_eq!(
+        header(0, EMPTY_MERKLE_ROOT, EMPTY_MERKLE_ROOT).validate(),
+        Ok(())
+    );
+    assert_eq!(header(1, root, EMPTY_MERKLE_ROOT).validate(), Ok(()));
+    assert_eq!(
+        header(0, root, EMPTY_MERKLE_ROOT).validate(),
+        Err(HeaderValidationError::CountRootMismatch {
+            field: "transactions_root",
+            root,
+        })
+    );
+    assert_eq!(
+        header(0, EMPTY_MERKLE_ROOT, root).validate(),
+        Err(HeaderValidationError::CountRootMismatch {
+            field: "message_receipt_root",
+            root,
+        })
+    );
+}
+
+#[test]
+fn validate_bounds_message_receipt_count() {
+    let header = |message_receipt_count| {
+        PartialBlockHeader::default().generate_with_roots(
+            0,
+            message_receipt_count,
+            EMPTY_MERKLE_ROOT,
+            [1u8; 32].into(),
+        )
+    };
+
+    assert_eq!(header(MAX_MESSAGES_PER_BLOCK).validate(), Ok(()));
+    assert_eq!(
+        header(MAX_MESSAGES_PER_BLOCK + 1).validate(),
+        Err(HeaderValidationError::TooManyMessages {
+            count: MAX_MESSAGES_PER_BLOCK + 1,
+            max: MAX_MESSAGES_PER_BLOCK,
+        })
+    );
+    assert!(header(u64::MAX).validate().is_err());
+}
+
+#[test]
+fn cow_block_header_reads_owned_and_borrowed_headers() {
+    fn read<'a>(header: impl Into<CowBlockHeader<'a>>) -> (BlockHeight, Tai64, BlockId) {
+        let header = header.into();
+        (*header.height(), header.time(), header.id())
+    }
+
+    let header = non_empty_header();
+    let expected = (*header.height(), header.time(), header.id());
+
+    let borrowed = CowBlockHeader::from(&header);
+    assert!(borrowed.is_borrowed());
+    assert_eq!(read(&header), expected);
+
+    let owned = CowBlockHeader::from(header.clone());
+    assert!(!owned.is_borrowed());
+    assert_eq!(owned, borrowed);
+    assert_eq!(read(header.clone()), expected);
+    assert_eq!(owned.into_owned(), header);
+}
+
+#[test]
+fn sort_key_orders_by_height_then_id() {
+    let mut headers = vec![];
+    for height in [256u32, 1, 0, 255, 65_536] {
+        for time in 0..3 {
+            headers.push(
+                PartialBlockHeaderBuilder::new()
+                    .height(height.into())
+                    .time(Tai64(time))
+                    .build()
+                    .generate(&[], &[]),
+            );
+        }
+    }
+
+    let mut by_key = headers.clone();
+    by_key.sort_by_key(BlockHeader::sort_key);
+    let mut by_height_and_id = headers;
+    by_height_and_id.sort_by_key(|header| (*header.height(), header.id()));
+
+    assert_eq!(by_key, by_height_and_id);
+    let key = by_key[0].sort_key();
+    assert_eq!(key.len(), SORT_KEY_SIZE);
+    assert_eq!(key[8..], *by_key[0].id().as_slice());
+}
+
+#[test]
+fn canonical_transactions_root_is_over_bytes_not_ids() {
+    use crate::fuel_tx::UniqueIdentifier;
+
+    let transactions = transactions(3);
+    let tx_ids: Vec<Bytes32> = transactions
+        .iter()
+        .map(|tx| tx.id(&Default::default()))
+        .collect();
+    let canonical =
+        PartialBlockHeader::default().generate(&transactions, &message_ids(2));
+    let from_ids =
+        PartialBlockHeader::default().generate_from_tx_ids(&tx_ids, &message_ids(2));
+
+    assert!(canonical.validate_transactions(&transactions));
+    assert!(!from_ids.validate_transactions(&transactions));
+    assert_ne!(canonical.transactions_root, from_ids.transactions_root);
+    assert_eq!(canonical.transactions_count, from_ids.transactions_count);
+    assert_eq!(
+        canonical.message_receipt_root,
+        from_ids.message_receipt_root
+    );
+    assert_eq!(from_ids.validate(), Ok(()));
+
+    let empty = PartialBlockHeader::default().generate_from_tx_ids(&[], &[]);
+    assert_eq!(empty, PartialBlockHeader::default().generate(&[], &[]));
+}
+
+#[test]
+fn transactions_proof_verifies_against_transactions_root() {
+    for count in [1, 2, 7] {
+        let transactions = transactions(count);
+        let header = PartialBlockHeader::default().generate(&transactions, &[]);
+
+        for (index, tx) in transactions.iter().enumerate() {
+            let (root, proof) = transactions_proof(&transactions, index)
+                .expect("The index is in the range");
+
+            assert_eq!(root, header.transactions_root);
+            assert!(fuel_merkle::binary::verify(
+                &root,
+                &tx.clone().to_bytes(),
+                &proof.proof_set,
+                proof.proof_index,
+                count,
+            ));
+        }
+    }
+}
+
+#[test]
+fn transactions_proof_returns_none_for_out_of_range_index() {
+    assert!(transactions_proof(&transactions(3), 3).is_none());
+    assert!(transactions_proof(&[], 0).is_none());
+}
+
+#[test]
+fn transactions_multiproof_verifies_against_shared_root() {
+    let count = 7;
+    let transactions = transactions(count);
+    let header = PartialBlockHeader::default().generate(&transactions, &[]);
+
+    let (root, proofs) = transactions_multiproof(&transactions, &[5, 0, 5, 3])
+        .expect("The indices are in the range");
+
+    assert_eq!(root, header.transactions_root);
+    assert_eq!(
+        proofs
+            .iter()
+            .map(|proof| proof.proof_index)
+            .collect::<Vec<_>>(),
+        vec![0, 3, 5]
+    );
+    for proof in proofs {
+        let tx = &transactions[proof.proof_index as usize];
+        assert!(fuel_merkle::binary::verify(
+            &root,
+            &tx.clone().to_bytes(),
+            &proof.proof_set,
+            proof.proof_index,
+            count,
+        ));
+        assert_eq!(
+            transactions_proof(&transactions, proof.proof_index as usize),
+            Some((root, proof))
+        );
+    }
+    assert!(transactions_multiproof(&transactions, &[1, 7]).is_none());
+}
+
+#[test]
+fn message_receipts_proof_verifies_against_message_receipt_root() {
+    for count in [1, 2, 5, 16] {
+        let message_ids = message_ids(count);
+        let header = PartialBlockHeader::default().generate(&[], &message_ids);
+
+        for (index, id) in message_ids.iter().enumerate() {
+            let (root, proof) = message_receipts_proof(&message_ids, index)
+                .expect("The index is in the range");
+
+            assert_eq!(root, header.message_receipt_root);
+            assert!(fuel_merkle::binary::verify(
+                &root,
+                id,
+                &proof.proof_set,
+                proof.proof_index,
+                count,
+            ));
+        }
+    }
+}
+
+#[test]
+fn message_receipts_proof_returns_none_without_messages() {
+    assert!(message_receipts_proof(&[], 0).is_none());
+    assert!(message_receipts_proof(&message_ids(2), 2).is_none());
+}
+
+#[cfg(not(feature = "multi-da"))]
+fn application_header(
+    version: HeaderVersion,
+) -> ApplicationHeader<GeneratedApplicationFields> {
+    ApplicationHeader {
+        version,
+        da_height: 1u64.into(),
+        generated: GeneratedApplicationFields {
+            transactions_count: 2,
+            message_receipt_count: 3,
+            transactions_root: [4u8; 32].into(),
+            message_receipt_root: [5u8; 32].into(),
+        },
+    }
+}
+
+// The `V0` hash is the same as before the introduction of the `version`.
+#[cfg(not(feature = "multi-da"))]
+#[test]
+fn application_hash_v0_is_stable() {
+    assert_eq!(
+        format!("{:x}", application_header(HeaderVersion::V0).hash()),
+        "436f8056a02ae5440edea04e958b0e1f1267d21e641e2d3cd285cff55e0842a3"
+    );
+}
+
+#[cfg(not(feature = "multi-da"))]
+#[test]
+fn application_hash_v1_is_stable() {
+    assert_eq!(
+        format!("{:x}", application_header(HeaderVersion::V1).hash()),
+        "8e48fa0ac124fdb842e68d8a9bfc5820b1bd9563feda27ddf1bf0265b314e276"
+    );
+}
+
+fn consensus_header(
+    #[cfg(feature = "nanos")] time_nanos: u32,
+) -> ConsensusHeader<GeneratedConsensusFields> {
+    ConsensusHeader {
+        prev_root: [0x11u8; 32].into(),
+        height: 42u32.into(),
+        time: Tai64::from_unix(1_700_000_600),
+        #[cfg(feature = "nanos")]
+        time_nanos,
+        generated: GeneratedConsensusFields {
+            application_hash: [0x22u8; 32].into(),
+        },
+    }
+}
+
+#[cfg(not(feature = "nanos"))]
+#[test]
+fn consensus_hash_with_second_resolution_is_stable() {
+    assert_eq!(
+        format!("{:x}", consensus_header().hash()),
+        "d230593bc55bb72030330bb02573512317ce73fb5d747230350009b6f5e3c8f5"
+    );
+}
+
+// With the `nanos` feature, the `time_nanos` is always a part of the preimage,
+// so even zero nanoseconds change the hash of the second resolution.
+#[cfg(feature = "nanos")]
+#[test]
+fn consensus_hash_with_nanosecond_resolution_is_stable() {
+    assert_eq!(
+        format!("{:x}", consensus_header(500_000_000).hash()),
+        "2aee2fc3c99a6ac5be9500dba5998c4abf6252c3a0cf7cec959ae337d50a2a9b"
+    );
+    assert_eq!(
+        format!("{:x}", consensus_header(0).hash()),
+        "52b45d4528da4d4cc6db851a6d1dd96be1674aed915c8b0297990b1fd7bb21c8"
+    );
+}
+
+#[cfg(feature = "nanos")]
+#[test]
+fn time_nanos_is_part_of_encodings() {
+    use tai64::Tai64N;
+
+    let header = PartialBlockHeaderBuilder::new()
+        .time_n(Tai64N(Tai64(2), 500_000_000))
+        .build()
+        .generate(&[], &[]);
+    assert_eq!(header.time_n(), Tai64N(Tai64(2), 500_000_000));
+    assert!(!header.is_default());
+
+    let bytes = header.to_canonical_bytes();
+    assert_eq!(
+        BlockHeader::from_canonical_bytes(&bytes),
+        Ok(header.clone())
+    );
+    let compact = header.try_to_compact().unwrap();
+    assert_eq!(BlockHeader::from_compact(&compact), Ok(header.clone()));
+    let row = BlockHeaderRow::from(&header);
+    assert_eq!(BlockHeader::try_from(row), Ok(header.clone()));
+
+    let mut invalid = compact;
+    let offset = COMPACT_HEADER_SIZE - 4;
+    invalid[offset..offset + 4].copy_from_slice(&1_000_000_000u32.to_be_bytes());
+    assert_eq!(
+        BlockHeader::from_compact(&invalid),
+        Err(CanonicalBytesError::InvalidTimeNanos(1_000_000_000))
+    );
+}
+
+#[test]
+fn builder_fields_round_trip_into_generated_header() {
+    let partial = PartialBlockHeaderBuilder::new()
+        .da_height(5u64.into())
+        .prev_root([6u8; 32].into())
+        .height(7u32.into())
+        .time(Tai64(8))
+        .build();
+
+    let header = partial.generate(&transactions(2), &message_ids(2));
+
+    assert_eq!(header.da_height, 5u64.into());
+    assert_eq!(header.prev_root(), &Bytes32::from([6u8; 32]));
+    assert_eq!(header.height(), &7u32.into());
+    assert_eq!(header.time(), Tai64(8));
+}
+
+#[test]
+fn metadata_is_populated_after_generate() {
+    let mut header = PartialBlockHeader::default().generate(&[], &[]);
+    assert_eq!(
+        header.metadata().map(BlockHeaderMetadata::id),
+        Some(header.hash())
+    );
+
+    header.metadata = None;
+    assert_eq!(header.metadata(), None);
+}
+
+#[test]
+fn stripped_header_has_the_same_id() {
+    let header = BlockHeader::new_block(3u32.into(), Tai64(4));
+
+    let stripped = header.clone().without_metadata();
+    assert_eq!(stripped.metadata(), None);
+    assert_eq!(stripped.id(), header.id());
+
+    let recalculated = stripped.with_recalculated_metadata();
+    assert_eq!(recalculated.metadata(), header.metadata());
+}
+
+fn non_empty_header() -> BlockHeader {
+    PartialBlockHeaderBuilder::new()
+        .da_height(1u64.into())
+        .prev_root([2u8; 32].into())
+        .height(3u32.into())
+        .time(Tai64(4))
+        .build()
+        .generate(&transactions(2), &message_ids(3))
+}
+
+#[test]
+fn canonical_bytes_round_trip() {
+    let header = non_empty_header();
+
+    let bytes = header.to_canonical_bytes();
+    assert_eq!(bytes.len(), CANONICAL_HEADER_SIZE);
+
+    let decoded = BlockHeader::from_canonical_bytes(&bytes).unwrap();
+    assert_eq!(decoded, header);
+    assert_eq!(decoded.metadata(), header.metadata());
+}
+
+#[test]
+fn serialized_size_hint_matches_canonical_bytes() {
+    let mut v1 = non_empty_header();
+    v1.application.version = HeaderVersion::V1;
+    v1.recalculate_metadata();
+    let headers = [
+        BlockHeader::default(),
+        non_empty_header(),
+        non_empty_header().without_metadata(),
+        v1,
+    ];
+
+    for header in headers {
+        assert_eq!(
+            header.serialized_size_hint(),
+            header.to_canonical_bytes().len()
+        );
+    }
+}
+
+#[cfg(not(any(feature = "multi-da", feature = "nanos")))]
+#[test]
+fn canonical_bytes_are_stable() {
+    let header = PartialBlockHeaderBuilder::new()
+        .height(1u32.into())
+        .time(Tai64(2))
+        .build()
+        .generate(&[], &[]);
+    let root = |hex: &str| Bytes32::from_str(hex).unwrap();
+    let empty_root =
+        root("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+    let application_hash =
+        root("d550bfbb5a2be7cefdcbffb321f65db28f195c4a9379f8254805a17ff873f609");
+
+    // The version, the `da_height` and the counts.
+    let mut expected = vec![0u8; 25];
+    expected.extend_from_slice(empty_root.as_ref());
+    expected.extend_from_slice(empty_root.as_ref());
+    // The previous root.
+    expected.extend_from_slice(&[0u8; 32]);
+    expected.extend_from_slice(&1u32.to_be_bytes());
+    expected.extend_from_slice(&2u64.to_be_bytes());
+    expected.extend_from_slice(application_hash.as_ref());
+
+    assert_eq!(header.to_canonical_bytes(), expected);
+}
+
+#[test]
+fn from_canonical_bytes_rejects_invalid_input() {
+    let bytes = non_empty_header().to_canonical_bytes();
+
+    assert_eq!(
+        BlockHeader::from_canonical_bytes(&bytes[1..]),
+        Err(CanonicalBytesError::InvalidLength(
+            CANONICAL_HEADER_SIZE - 1
+        ))
+    );
+
+    let mut unknown_version = bytes.clone();
+    unknown_version[0] = 2;
+    assert_eq!(
+        BlockHeader::from_canonical_bytes(&unknown_version),
+        Err(CanonicalBytesError::UnknownVersion(2))
+    );
+
+    let mut tampered = bytes;
+    tampered[1] = 1;
+    assert!(matches!(
+        BlockHeader::from_canonical_bytes(&tampered),
+        Err(CanonicalBytesError::InvalidHeader(_))
+    ));
+}
+
+#[test]
+fn prev_and_next_heights() {
+    let header = BlockHeader::new_block(5u32.into(), Tai64(0));
+    assert_eq!(header.prev_height(), Some(4u32.into()));
+    assert_eq!(header.next_height(), Some(6u32.into()));
+
+    let genesis = BlockHeader::new_block(0u32.into(), Tai64(0));
+    assert_eq!(genesis.prev_height(), None);
+    assert_eq!(genesis.next_height(), Some(1u32.into()));
+
+    let last = BlockHeader::new_block(u32::MAX.into(), Tai64(0));
+    assert_eq!(last.prev_height(), Some((u32::MAX - 1).into()));
+    assert_eq!(last.next_height(), None);
+}
+
+#[test]
+fn unix_timestamp_conversion() {
+    let header = BlockHeader::new_block(0u32.into(), Tai64::UNIX_EPOCH);
+    assert_eq!(header.unix_timestamp(), 0);
+
+    // 2023-11-14T22:13:20Z
+    let header = BlockHeader::new_block(0u32.into(), Tai64(0x400000006553f10a));
+    assert_eq!(header.unix_timestamp(), 1_700_000_000);
+
+    for secs in [-1_000, 0, 1_700_000_000] {
+        let header = PartialBlockHeaderBuilder::new()
+            .unix_timestamp(secs)
+            .build()
+            .generate(&[], &[]);
+        assert_eq!(header.unix_timestamp(), secs);
+    }
+}
+
+#[test]
+fn partial_and_full_headers_return_time_by_value() {
+    let header = non_empty_header();
+    let partial = header.to_partial();
+
+    let partial_time: Tai64 = partial.time();
+    let time: Tai64 = header.time();
+    assert_eq!(partial_time, time);
+    assert_eq!(partial_time, Tai64(4));
+}
+
+#[test]
+fn time_since_returns_signed_interval() {
+    let parent = BlockHeader::new_block(1u32.into(), Tai64::from_unix(1_700_000_000));
+    let child = BlockHeader::new_block(2u32.into(), Tai64::from_unix(1_700_000_012));
+
+    assert_eq!(child.time_since(&parent), 12);
+    // The chain with the time going back.
+    assert_eq!(parent.time_since(&child), -12);
+    assert_eq!(child.time_since(&child), 0);
+}
+
+#[test]
+fn time_strictly_after_rejects_equal_time() {
+    let earlier = BlockHeader::new_block(1u32.into(), Tai64::from_unix(1_700_000_000));
+    let later = BlockHeader::new_block(2u32.into(), Tai64::from_unix(1_700_000_001));
+    let same_time = BlockHeader::new_block(2u32.into(), earlier.time());
+
+    assert!(later.time_strictly_after(&earlier));
+    assert!(!same_time.time_strictly_after(&earlier));
+    assert!(!earlier.time_strictly_after(&later));
+}
+
+#[test]
+fn is_fresh_rejects_only_old_headers() {
+    let now = Tai64::from_unix(1_700_000_000);
+    let header = |secs| BlockHeader::new_block(1u32.into(), Tai64::from_unix(secs));
+
+    assert!(header(1_700_000_000).is_fresh(now, 0));
+    assert!(header(1_699_999_990).is_fresh(now, 10));
+    // Stale.
+    assert!(!header(1_699_999_989).is_fresh(now, 10));
+    assert!(!header(0).is_fresh(now, 10));
+    // The header from the future.
+    assert!(header(1_700_000_100).is_fresh(now, 0));
+    assert!(BlockHeader::new_block(1u32.into(), Tai64(MAX_TAI64)).is_fresh(now, 0));
+}
+
+#[test]
+fn headers_are_ordered_by_height_then_time() {
+    let headers = [
+        BlockHeader::new_block(3u32.into(), Tai64(1)),
+        BlockHeader::new_block(1u32.into(), Tai64(5)),
+        BlockHeader::new_block(2u32.into(), Tai64(2)),
+        BlockHeader::new_block(1u32.into(), Tai64(4)),
+        BlockHeader::new_block(2u32.into(), Tai64(2)),
+    ];
+
+    let set: std::collections::BTreeSet<_> = headers.into_iter().collect();
+    let order: Vec<_> = set.iter().map(|h| (**h.height(), h.time().0)).collect();
+
+    assert_eq!(order, vec![(1, 4), (1, 5), (2, 2), (3, 1)]);
+}
+
+#[test]
+fn order_is_consistent_with_eq() {
+    use core::cmp::Ordering;
+
+    let header = non_empty_header();
+
+    // The stale cached id doesn't affect the order of equal headers.
+    let mut stale = header.clone();
+    stale.metadata = Some(BlockHeaderMetadata::new(BlockId::from([1u8; 32])));
+    assert_eq!(stale, header);
+    assert_eq!(stale.cmp(&header), Ordering::Equal);
+
+    assert_eq!(hash_of(&stale), hash_of(&header));
+
+    // The headers with the same id, but different application fields, are not equal.
+    let mut mismatched = header.clone();
+    mismatched.application.da_height = 10u64.into();
+    assert_eq!(mismatched.consensus.hash(), header.id());
+    assert_ne!(mismatched, header);
+    assert_ne!(mismatched.cmp(&header), Ordering::Equal);
+    assert_eq!(mismatched.cmp(&header), header.cmp(&mismatched).reverse());
+
+    let set: std::collections::BTreeSet<_> =
+        [header.clone(), stale.clone(), mismatched.clone()]
+            .into_iter()
+            .collect();
+    assert_eq!(set.len(), 2);
+    let set: std::collections::HashSet<_> =
+        [header, stale, mismatched].into_iter().collect();
+    assert_eq!(set.len(), 2);
+}
+
+fn hash_of(header: &BlockHeader) -> u64 {
+    use core::hash::{
+        Hash,
+        Hasher,
+    };
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    Hash::hash(header, &mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn is_genesis_requires_zero_height_and_zero_prev_root() {
+    let genesis = BlockHeader::new_block(0u32.into(), Tai64::UNIX_EPOCH);
+    assert!(genesis.is_genesis());
+
+    let mut with_prev_root = genesis.clone();
+    with_prev_root.consensus.prev_root = [1u8; 32].into();
+    assert!(!with_prev_root.is_genesis());
+
+    let not_zero_height = BlockHeader::new_block(1u32.into(), Tai64::UNIX_EPOCH);
+    assert!(!not_zero_height.is_genesis());
+}
+
+#[test]
+fn verify_prev_root_accepts_linked_headers() {
+    let headers = chain_of(5);
+    let mut accumulator = PrevRootBuilder::new();
+
+    for pair in headers.windows(2) {
+        let (parent, child) = (&pair[0], &pair[1]);
+        assert!(child.consensus.verify_prev_root(&parent.id(), &accumulator));
+        accumulator.push(&parent.id());
+    }
+}
+
+#[test]
+fn verify_prev_root_rejects_wrong_parent() {
+    let headers = chain_of(3);
+    let mut accumulator = PrevRootBuilder::new();
+    accumulator.push(&headers[0].id());
+
+    assert!(!headers[2]
+        .consensus
+        .verify_prev_root(&headers[0].id(), &accumulator));
+    assert!(!headers[1]
+        .consensus
+        .verify_prev_root(&headers[0].id(), &accumulator));
+}
+
+#[test]
+fn transaction_root_accumulator_matches_generate() {
+    for count in [0, 1, 3, 10] {
+        let transactions = transactions(count);
+        let header = PartialBlockHeader::default().generate(&transactions, &[]);
+
+        let mut accumulator = TransactionRootAccumulator::new();
+        for tx in &transactions {
+            accumulator.push(&tx.clone().to_bytes());
+        }
+
+        assert_eq!(accumulator.finalize(), header.transactions_root);
+    }
+}
+
+#[test]
+fn generate_with_roots_matches_generate() {
+    let transactions = transactions(2);
+    let message_ids = message_ids(3);
+    let partial = PartialBlockHeaderBuilder::new()
+        .da_height(1u64.into())
+        .height(3u32.into())
+        .build();
+
+    let generated = partial.clone().generate(&transactions, &message_ids);
+    let with_roots = partial.generate_with_roots(
+        generated.transactions_count,
+        generated.message_receipt_count,
+        generated.transactions_root,
+        generated.message_receipt_root,
+    );
+
+    assert_eq!(generated, with_roots);
+    assert_eq!(generated.id(), with_roots.id());
+    assert_eq!(with_roots.validate(), Ok(()));
+}
+
+#[test]
+fn partial_application_hash_matches_generated_header() {
+    let transactions = transactions(2);
+    let message_ids = message_ids(3);
+
+    for version in [HeaderVersion::V0, HeaderVersion::V1] {
+        let partial = PartialBlockHeaderBuilder::new()
+            .version(version)
+            .da_height(1u64.into())
+            .build();
+
+        let application_hash = partial.application_hash(&transactions, &message_ids);
+        let header = partial.generate(&transactions, &message_ids);
+
+        assert_eq!(&application_hash, header.application_hash());
+    }
+}
+
+#[test]
+fn consensus_header_hash_matches_block_id() {
+    let header = non_empty_header();
+    let consensus = ConsensusHeader {
+        prev_root: *header.prev_root(),
+        height: *header.height(),
+        time: header.time(),
+        #[cfg(feature = "nanos")]
+        time_nanos: header.consensus.time_nanos,
+        generated: GeneratedConsensusFields {
+            application_hash: *header.application_hash(),
+        },
+    };
+
+    assert_eq!(consensus.hash(), header.hash());
+    assert_eq!(consensus.hash(), header.id());
+}
+
+#[test]
+fn verify_da_progression_checks_direction_and_gap() {
+    let header_at = |da_height: u64| {
+        PartialBlockHeaderBuilder::new()
+            .da_height(da_height.into())
+            .build()
+            .generate(&[], &[])
+    };
+    let parent = header_at(10);
+
+    // Equal
+    assert_eq!(
+        header_at(10)
+            .application
+            .verify_da_progression(&parent.application, 5),
+        Ok(())
+    );
+    // Increasing within the gap
+    assert_eq!(
+        header_at(15)
+            .application
+            .verify_da_progression(&parent.application, 5),
+        Ok(())
+    );
+    // Decreasing
+    assert_eq!(
+        header_at(9)
+            .application
+            .verify_da_progression(&parent.application, 5),
+        Err(DaHeightError::Decreasing {
+            parent: 10u64.into(),
+            current: 9u64.into(),
+        })
+    );
+    // Too large jump
+    assert_eq!(
+        header_at(16)
+            .application
+            .verify_da_progression(&parent.application, 5),
+        Err(DaHeightError::GapTooLarge {
+            parent: 10u64.into(),
+            current: 16u64.into(),
+            max_gap: 5,
+        })
+    );
+}
+
+#[test]
+fn time_checked_rejects_far_future_times() {
+    let now = Tai64(100);
+    let mut builder = PartialBlockHeaderBuilder::new();
+
+    builder.time_checked(Tai64(110), now, 10).unwrap();
+    assert_eq!(builder.build().time(), Tai64(110));
+    // The past times are not restricted.
+    builder.time_checked(Tai64(50), now, 10).unwrap();
+    assert_eq!(builder.build().time(), Tai64(50));
+
+    assert_eq!(
+        builder.time_checked(Tai64(111), now, 10).err(),
+        Some(TimeError::TooFarInFuture {
+            now,
+            time: Tai64(111),
+            max_future_secs: 10,
+        })
+    );
+    assert_eq!(builder.build().time(), Tai64(50));
+}
+
+#[test]
+fn verify_time_progression_checks_direction_and_drift() {
+    let header_at = |time: u64| {
+        PartialBlockHeaderBuilder::new()
+            .time(Tai64(time))
+            .build()
+            .generate(&[], &[])
+    };
+    let parent_time = Tai64(100);
+
+    // Equal
+    assert_eq!(
+        header_at(100)
+            .consensus
+            .verify_time_progression(parent_time, 5),
+        Ok(())
+    );
+    // Increasing within the drift
+    assert_eq!(
+        header_at(105)
+            .consensus
+            .verify_time_progression(parent_time, 5),
+        Ok(())
+    );
+    // Backwards
+    assert_eq!(
+        header_at(99)
+            .consensus
+            .verify_time_progression(parent_time, 5),
+        Err(TimeError::NonMonotonic {
+            parent: parent_time,
+            current: Tai64(99),
+        })
+    );
+    // Too large jump
+    assert_eq!(
+        header_at(106)
+            .consensus
+            .verify_time_progression(parent_time, 5),
+        Err(TimeError::ExcessiveDrift {
+            parent: parent_time,
+            current: Tai64(106),
+            max_drift_secs: 5,
+        })
+    );
+}
+
+#[test]
+fn summary_identifies_header() {
+    let header = non_empty_header();
+    let summary = header.summary();
+
+    assert_eq!(summary.id, header.id());
+    assert_eq!(&summary.height, header.height());
+    assert_eq!(&summary.prev_root, header.prev_root());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn summary_serde_roundtrip_is_smaller_than_header() {
+    let header = non_empty_header();
+    let summary = header.summary();
+
+    let summary_bytes = postcard::to_allocvec(&summary).unwrap();
+    let header_bytes = postcard::to_allocvec(&header).unwrap();
+    let decoded: HeaderSummary = postcard::from_bytes(&summary_bytes).unwrap();
+
+    assert_eq!(decoded, summary);
+    assert!(summary_bytes.len() < header_bytes.len());
+}
+
+#[cfg(feature = "serde")]
+#[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+struct Rfc3339Time {
+    #[serde(with = "serde_rfc3339")]
+    time: Tai64,
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn rfc3339_time_json_roundtrip() {
+    for (unix, formatted) in [
+        (0, "1970-01-01T00:00:00Z"),
+        (-1, "1969-12-31T23:59:59Z"),
+        (951_782_400, "2000-02-29T00:00:00Z"),
+        (1_682_942_400, "2023-05-01T12:00:00Z"),
+    ] {
+        let value = Rfc3339Time {
+            time: Tai64::from_unix(unix),
+        };
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(json, format!(r#"{{"time":"{formatted}"}}"#));
+        assert_eq!(serde_json::from_str::<Rfc3339Time>(&json).unwrap(), value);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn rfc3339_time_accepts_offsets() {
+    let parsed: Rfc3339Time =
+        serde_json::from_str(r#"{"time":"2023-05-01T14:30:00+02:30"}"#).unwrap();
+    assert_eq!(parsed.time, Tai64::from_unix(1_682_942_400));
+
+    let parsed: Rfc3339Time =
+        serde_json::from_str(r#"{"time":"2023-05-01T11:00:00-01:00"}"#).unwrap();
+    assert_eq!(parsed.time, Tai64::from_unix(1_682_942_400));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn rfc3339_time_rejects_malformed_strings() {
+    for malformed in [
+        "",
+        "2023-05-01",
+        "2023-05-01 12:00:00Z",
+        "2023-05-01T12:00:00",
+        "2023-05-01T12:00:00.5Z",
+        "2023-13-01T12:00:00Z",
+        "2023-02-29T12:00:00Z",
+        "2023-05-01T24:00:00Z",
+        "2023-05-01T12:00:00+25:00",
+        "2023-o5-01T12:00:00Z",
+    ] {
+        let json = format!(r#"{{"time":"{malformed}"}}"#);
+        assert!(
+            serde_json::from_str::<Rfc3339Time>(&json).is_err(),
+            "{malformed} should be rejected"
+        );
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn rfc3339_time_keeps_binary_representation() {
+    let time = Tai64::from_unix(1_682_942_400);
+    let bytes = postcard::to_allocvec(&Rfc3339Time { time }).unwrap();
+
+    assert_eq!(bytes, postcard::to_allocvec(&time).unwrap());
+    assert_eq!(
+        postcard::from_bytes::<Rfc3339Time>(&bytes).unwrap(),
+        Rfc3339Time { time }
+    );
+}
+
+#[test]
+fn into_partial_regenerates_same_header() {
+    let transactions = transactions(2);
+    let message_ids = message_ids(3);
+    let header = non_empty_header();
+
+    let partial = header.clone().into_partial();
+    assert_eq!(partial.prev_root(), header.prev_root());
+    assert_eq!(partial.height(), header.height());
+    assert_eq!(partial.time(), header.time());
+
+    let regenerated = partial.generate(&transactions, &message_ids);
+    assert_eq!(regenerated, header);
+    assert_eq!(regenerated.id(), header.id());
+}
+
+#[test]
+fn generate_checked_rejects_duplicate_message_ids() {
+    let mut message_ids = message_ids(3);
+    message_ids.push(message_ids[1]);
+
+    let lenient = PartialBlockHeader::default().generate(&[], &message_ids);
+    assert_eq!(lenient.message_receipt_count, 4);
+
+    assert_eq!(
+        PartialBlockHeader::default().generate_checked(&[], &message_ids),
+        Err(GenerateError::DuplicateMessageId(message_ids[1]))
+    );
+}
+
+#[test]
+fn generate_checked_matches_generate_for_unique_message_ids() {
+    let transactions = transactions(2);
+    let message_ids = message_ids(3);
+
+    assert_eq!(
+        PartialBlockHeader::default().generate_checked(&transactions, &message_ids),
+        Ok(PartialBlockHeader::default().generate(&transactions, &message_ids))
+    );
+}
+
+// The empty blocks skip building the merkle trees and use `EMPTY_MERKLE_ROOT` instead.
+// It saves the allocation of two trees on every empty block, which is the most
+// common block on an idle network.
+#[test]
+fn empty_block_uses_empty_tree_root() {
+    let tree_root: Bytes32 = fuel_merkle::binary::in_memory::MerkleTree::new()
+        .root()
+        .into();
+    assert_eq!(EMPTY_MERKLE_ROOT, tree_root);
+
+    let header = PartialBlockHeader::default().generate(&[], &[]);
+    assert_eq!(header.transactions_root, tree_root);
+    assert_eq!(header.message_receipt_root, tree_root);
+}
+
+#[test]
+fn block_header_row_round_trip() {
+    let header = non_empty_header();
+
+    let row = BlockHeaderRow::from(&header);
+    assert_eq!(row.id, header.id());
+    assert_eq!(&row.application_hash, header.application_hash());
+
+    let decoded = BlockHeader::try_from(row.clone()).unwrap();
+    assert_eq!(decoded, header);
+    assert_eq!(decoded.metadata(), header.metadata());
+    assert_eq!(BlockHeaderRow::from(&decoded), row);
+}
+
+#[test]
+fn block_header_row_rejects_inconsistent_hashes() {
+    let header = non_empty_header();
+
+    let mut row = BlockHeaderRow::from(&header);
+    row.application_hash = Bytes32::zeroed();
+    assert_eq!(
+        BlockHeader::try_from(row),
+        Err(HeaderValidationError::ApplicationHashMismatch {
+            expected: *header.application_hash(),
+            actual: Bytes32::zeroed(),
+        })
+    );
+
+    let mut row = BlockHeaderRow::from(&header);
+    row.id = BlockId::from([0u8; 32]);
+    assert_eq!(
+        BlockHeader::try_from(row),
+        Err(HeaderValidationError::StaleMetadata {
+            cached: BlockId::from([0u8; 32]),
+            actual: header.id(),
+        })
+    );
+}
+
+#[test]
+fn display_shows_height_and_id() {
+    let header = non_empty_header();
+
+    let formatted = header.to_string();
+    assert!(formatted.starts_with("block#3 id=0x"));
+    assert!(formatted.ends_with(&format!("{:x}", header.id())));
+    assert_eq!(header.clone().into_partial().to_string(), "block#3");
+}
+
+#[test]
+fn short_id_is_prefix_of_id() {
+    let header = non_empty_header();
+
+    let short_id = header.short_id();
+    assert_eq!(short_id.len(), 8);
+    assert!(format!("{:x}", header.id()).starts_with(&short_id));
+}
+
+#[test]
+fn compute_id_ignores_stale_metadata() {
+    let mut header = non_empty_header();
+    assert_eq!(header.compute_id(), header.id());
+
+    let stale = BlockId::from([1u8; 32]);
+    header.metadata = Some(BlockHeaderMetadata::new(stale));
+
+    assert_eq!(header.id(), stale);
+    assert_ne!(header.compute_id(), header.id());
+    assert_eq!(header.compute_id(), header.consensus.hash());
+}
+
+#[test]
+fn default_metadata_can_be_attached_to_header() {
+    let metadata = BlockHeaderMetadata::default();
+    assert_eq!(metadata.id(), BlockId::default());
+
+    let header = BlockHeader {
+        metadata: Some(metadata.clone()),
+        ..non_empty_header()
+    };
+    assert_eq!(header.metadata(), Some(&metadata));
+    assert_eq!(header.id(), BlockId::default());
+}
+
+#[test]
+fn try_generate_matches_generate() {
+    let transactions = transactions(2);
+    let message_ids = message_ids(3);
+
+    assert_eq!(
+        PartialBlockHeader::default().try_generate(&transactions, &message_ids),
+        Ok(PartialBlockHeader::default().generate(&transactions, &message_ids))
+    );
+}
+
+#[test]
+fn check_counts_accepts_transactions_fitting_u64_and_bounded_messages() {
+    let max_messages = MAX_MESSAGES_PER_BLOCK as usize;
+    assert_eq!(check_counts(0, 0), Ok(()));
+    assert_eq!(check_counts(usize::MAX, max_messages), Ok(()));
+    if let Ok(too_many) = usize::try_from(MAX_MESSAGES_PER_BLOCK + 1) {
+        assert_eq!(
+            check_counts(0, too_many),
+            Err(GenerateError::TooManyMessages(too_many))
+        );
+    }
+}
+
+// The slices longer than `u64::MAX` are possible only on the platforms
+// with pointers wider than 64 bits.
+#[cfg(not(any(
+    target_pointer_width = "16",
+    target_pointer_width = "32",
+    target_pointer_width = "64"
+)))]
+#[test]
+fn check_counts_rejects_counts_overflowing_u64() {
+    let too_many = u64::MAX as usize + 1;
+    assert_eq!(
+        check_counts(too_many, 0),
+        Err(GenerateError::TooManyTransactions(too_many))
+    );
+    assert_eq!(
+        check_counts(0, too_many),
+        Err(GenerateError::TooManyMessages(too_many))
+    );
+}
+
+#[test]
+fn da_height_range_covers_parent_to_child() {
+    let header_at = |da_height: u64| {
+        PartialBlockHeaderBuilder::new()
+            .da_height(da_height.into())
+            .build()
+            .generate(&[], &[])
+    };
+    let parent = header_at(10);
+
+    let range = header_at(15).da_height_range(&parent);
+    assert_eq!(*range.start(), 10u64.into());
+    assert_eq!(*range.end(), 15u64.into());
+
+    let range = header_at(10).da_height_range(&parent);
+    assert_eq!(range, 10u64.into()..=10u64.into());
+    assert!(range.contains(&10u64.into()));
+}
+
+#[cfg(feature = "arbitrary")]
+#[test]
+fn arbitrary_headers_are_valid() {
+    use ::arbitrary::{
+        Arbitrary,
+        Unstructured,
+    };
+
+    for seed in 0u64..1000 {
+        // Deterministic pseudo-random input for the fuzzer.
+        let data: Vec<u8> = (0u64..8)
+            .flat_map(|i| {
+                *crate::fuel_crypto::Hasher::hash(
+                    [seed.to_be_bytes(), i.to_be_bytes()].concat(),
+                )
+            })
+            .collect();
+        let mut u = Unstructured::new(&data);
+
+        let header = BlockHeader::arbitrary(&mut u).unwrap();
+        assert_eq!(header.validate(), Ok(()), "seed {seed}");
+        assert_eq!(header.id(), header.consensus.hash());
+    }
+}
+
+#[test]
+fn ct_eq_compares_hashes() {
+    let hash = Bytes32::from([7u8; 32]);
+    let mut near_miss = hash;
+    near_miss[31] ^= 1;
+
+    assert!(ct_eq(hash, hash));
+    assert!(!ct_eq(hash, near_miss));
+    assert!(!ct_eq(hash, Bytes32::zeroed()));
+    assert!(ct_eq(BlockId::from([7u8; 32]), hash));
+}
+
+#[test]
+fn generate_from_iter_matches_generate() {
+    for (tx_count, message_count) in [(0, 0), (1, 0), (0, 1), (2, 3), (5, 7)] {
+        let transactions = transactions(tx_count);
+        let message_ids = message_ids(message_count);
+        let partial = PartialBlockHeaderBuilder::new().height(3u32.into()).build();
+
+        let expected = partial.clone().generate(&transactions, &message_ids);
+        let actual = partial.generate_from_iter(
+            transactions.iter().map(|tx| tx.clone().to_bytes()),
+            message_ids.iter().copied(),
+        );
+
+        assert_eq!(actual, expected);
+        assert_eq!(actual.id(), expected.id());
+    }
+}
+
+#[test]
+fn application_hash_from_raw_fields_matches_header_hash() {
+    let header = non_empty_header();
+    let application = &header.application;
+
+    assert_eq!(
+        application_hash(
+            application.da_height,
+            application.transactions_count,
+            application.message_receipt_count,
+            &application.transactions_root,
+            &application.message_receipt_root,
+        ),
+        application.hash()
+    );
+}
+
+#[test]
+fn block_id_from_raw_fields_matches_consensus_hash() {
+    let header = non_empty_header();
+    let consensus = &header.consensus;
+
+    assert_eq!(
+        block_id(
+            &consensus.prev_root,
+            consensus.height,
+            consensus.time,
+            &consensus.application_hash,
+        ),
+        consensus.hash()
+    );
+}
+
+#[cfg(not(feature = "nanos"))]
+#[test]
+fn block_id_from_raw_fields_is_stable() {
+    let id = block_id(
+        &Bytes32::from([1u8; 32]),
+        2u32.into(),
+        Tai64(3),
+        &Bytes32::from([4u8; 32]),
+    );
+
+    assert_eq!(
+        id,
+        BlockId::from_str(
+            "0xf40bd2c3349f2690795f1fc98616436b77e3ed7071f4bd55638e8fb40170115a"
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn validate_genesis_accepts_well_formed_genesis() {
+    let genesis = PartialBlockHeader::default().generate(&[], &[]);
+    assert_eq!(genesis.validate_genesis(), Ok(()));
+
+    // The headers above the genesis are not constrained.
+    let header = non_empty_header();
+    assert_eq!(header.validate_genesis(), Ok(()));
+}
+
+#[test]
+fn validate_genesis_rejects_malformed_genesis() {
+    let cases = [
+        (1u64, Bytes32::zeroed()),
+        (0, Bytes32::from([1u8; 32])),
+        (1, Bytes32::from([1u8; 32])),
+    ];
+    for (da_height, prev_root) in cases {
+        let genesis = PartialBlockHeaderBuilder::new()
+            .da_height(da_height.into())
+            .prev_root(prev_root)
+            .build()
+            .generate(&[], &[]);
+
+        assert_eq!(genesis.validate(), Ok(()));
+        assert_eq!(
+            genesis.validate_genesis(),
+            Err(HeaderValidationError::GenesisConstraintViolated {
+                da_height: da_height.into(),
+                prev_root,
+            })
+        );
+    }
+}
+
+#[test]
+fn transactions_tree_depth_matches_proof_length() {
+    for (count, depth) in [(0, 0), (1, 0), (2, 1), (3, 2), (8, 3), (9, 4)] {
+        let transactions = transactions(count);
+        let header = PartialBlockHeader::default().generate(&transactions, &[]);
+        assert_eq!(header.transactions_tree_depth(), depth, "count {count}");
+
+        if count > 0 {
+            let (_, proof) = transactions_proof(&transactions, 0).unwrap();
+            assert_eq!(proof.proof_set.len() as u32, depth, "count {count}");
+        }
+    }
+}
+
+#[test]
+fn combined_root_is_stable() {
+    let generated = GeneratedApplicationFields {
+        transactions_count: 1,
+        message_receipt_count: 1,
+        transactions_root: [1u8; 32].into(),
+        message_receipt_root: [2u8; 32].into(),
+    };
+    let expected = Bytes32::from_str(
+        "0xf818afd37a6dc3bc92fb44731011277006db4efa6e9023cd7468c02335d22a4d",
+    )
+    .unwrap();
+
+    assert_eq!(generated.combined_root(), expected);
+    // The counts are not committed.
+    let generated = GeneratedApplicationFields {
+        transactions_count: 2,
+        ..generated
+    };
+    assert_eq!(generated.combined_root(), expected);
+}
+
+#[test]
+fn fields_diff_distinguishes_counts_from_roots() {
+    let generated = GeneratedApplicationFields {
+        transactions_count: 1,
+        message_receipt_count: 1,
+        transactions_root: [1u8; 32].into(),
+        message_receipt_root: [2u8; 32].into(),
+    };
+    assert!(generated.roots_equal(&generated));
+    assert_eq!(
+        generated.fields_diff(&generated),
+        ApplicationFieldsDiff::default()
+    );
+
+    let only_counts = GeneratedApplicationFields {
+        transactions_count: 2,
+        message_receipt_count: 3,
+        ..generated.clone()
+    };
+    assert!(generated.roots_equal(&only_counts));
+    assert_eq!(
+        generated.fields_diff(&only_counts),
+        ApplicationFieldsDiff {
+            transactions_count: true,
+            message_receipt_count: true,
+            transactions_root: false,
+            message_receipt_root: false,
+        }
+    );
+
+    let counts_and_roots = GeneratedApplicationFields {
+        transactions_root: [3u8; 32].into(),
+        message_receipt_root: [4u8; 32].into(),
+        ..only_counts
+    };
+    assert!(!generated.roots_equal(&counts_and_roots));
+    assert_eq!(
+        generated.fields_diff(&counts_and_roots),
+        ApplicationFieldsDiff {
+            transactions_count: true,
+            message_receipt_count: true,
+            transactions_root: true,
+            message_receipt_root: true,
+        }
+    );
+}
+
+#[test]
+fn diff_reports_each_changed_field() {
+    let header = non_empty_header();
+    assert_eq!(header.diff(&header), HeaderDiff::default());
+    assert_eq!(
+        header.diff(&header.clone().without_metadata()),
+        HeaderDiff::default()
+    );
+
+    type Change = fn(&mut BlockHeader);
+    let cases: [(Change, HeaderDiff); 10] = [
+        (
+            |h| h.application.version = HeaderVersion::V1,
+            HeaderDiff {
+                version: true,
+                ..Default::default()
+            },
+        ),
+        (
+            |h| h.application.da_height = 10u64.into(),
+            HeaderDiff {
+                da_height: true,
+                ..Default::default()
+            },
+        ),
+        (
+            |h| h.application.generated.transactions_count = 10,
+            HeaderDiff {
+                generated: ApplicationFieldsDiff {
+                    transactions_count: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ),
+        (
+            |h| h.application.generated.message_receipt_count = 10,
+            HeaderDiff {
+                generated: ApplicationFieldsDiff {
+                    message_receipt_count: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ),
+        (
+            |h| h.application.generated.transactions_root = [10u8; 32].into(),
+            HeaderDiff {
+                generated: ApplicationFieldsDiff {
+                    transactions_root: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ),
+        (
+            |h| h.application.generated.message_receipt_root = [10u8; 32].into(),
+            HeaderDiff {
+                generated: ApplicationFieldsDiff {
+                    message_receipt_root: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ),
+        (
+            |h| h.consensus.prev_root = [10u8; 32].into(),
+            HeaderDiff {
+                prev_root: true,
+                ..Default::default()
+            },
+        ),
+        (
+            |h| h.consensus.height = 10u32.into(),
+            HeaderDiff {
+                height: true,
+                ..Default::default()
+            },
+        ),
+        (
+            |h| h.consensus.time = Tai64(10),
+            HeaderDiff {
+                time: true,
+                ..Default::default()
+            },
+        ),
+        (
+            |h| h.consensus.generated.application_hash = [10u8; 32].into(),
+            HeaderDiff {
+                application_hash: true,
+                ..Default::default()
+            },
+        ),
+    ];
+    for (change, expected) in cases {
+        let mut other = header.clone();
+        change(&mut other);
+        assert_eq!(header.diff(&other), expected);
+        assert_eq!(other.diff(&header), expected);
+    }
+}
+
+#[test]
+fn merge_of_fields_diffs_is_commutative_and_idempotent() {
+    let diffs: Vec<_> = (0u8..16)
+        .map(|bits| ApplicationFieldsDiff {
+            transactions_count: bits & 1 != 0,
+            message_receipt_count: bits & 2 != 0,
+            transactions_root: bits & 4 != 0,
+            message_receipt_root: bits & 8 != 0,
+        })
+        .collect();
+
+    for &a in &diffs {
+        assert_eq!(a.merge(a), a);
+        assert_eq!(a.merge(ApplicationFieldsDiff::default()), a);
+        for &b in &diffs {
+            assert_eq!(a.merge(b), b.merge(a));
+        }
+    }
+
+    let only_counts = ApplicationFieldsDiff {
+        transactions_count: true,
+        message_receipt_count: true,
+        ..Default::default()
+    };
+    let only_transactions_root = ApplicationFieldsDiff {
+        transactions_root: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        only_counts.merge(only_transactions_root),
+        ApplicationFieldsDiff {
+            transactions_count: true,
+            message_receipt_count: true,
+            transactions_root: true,
+            message_receipt_root: false,
+        }
+    );
+}
+
+#[test]
+fn is_default_detects_only_zeroed_header() {
+    let default = BlockHeader::default();
+    assert!(default.is_default());
+    assert!(default.without_metadata().is_default());
+
+    assert!(!non_empty_header().is_default());
+    assert!(!PartialBlockHeader::default()
+        .generate(&[], &[])
+        .is_default());
+    assert!(!BlockHeader::new_block(1u32.into(), Tai64::UNIX_EPOCH).is_default());
+}
+
+#[test]
+fn streaming_verification_matches_generated_root() {
+    for count in [0, 1, 2, 7] {
+        let transactions = transactions(count);
+        let header = PartialBlockHeader::default().generate(&transactions, &[]);
+        let bytes = || transactions.iter().map(|tx| tx.clone().to_bytes());
+
+        assert!(verify_transactions_root_streaming(
+            &header.transactions_root,
+            bytes()
+        ));
+        if count > 0 {
+            assert!(!verify_transactions_root_streaming(
+                &header.transactions_root,
+                bytes().skip(1)
+            ));
+        }
+    }
+}
+
+#[test]
+fn error_messages_are_non_empty_and_distinct() {
+    let root = Bytes32::from([1u8; 32]);
+    let header_error = HeaderValidationError::ApplicationHashMismatch {
+        expected: root,
+        actual: Bytes32::zeroed(),
+    };
+    let errors: Vec<Box<dyn std::error::Error>> = vec![
+        Box::new(header_error.clone()),
+        Box::new(HeaderValidationError::StaleMetadata {
+            cached: BlockId::from([1u8; 32]),
+            actual: BlockId::from([2u8; 32]),
+        }),
+        Box::new(HeaderValidationError::GenesisConstraintViolated {
+            da_height: 1u64.into(),
+            prev_root: root,
+        }),
+        Box::new(HeaderValidationError::CountRootMismatch {
+            field: "transactions_root",
+            root,
+        }),
+        Box::new(HeaderValidationError::TooManyMessages {
+            count: MAX_MESSAGES_PER_BLOCK + 1,
+            max: MAX_MESSAGES_PER_BLOCK,
+        }),
+        Box::new(ChainValidationError::InvalidHeader {
+            index: 1,
+            error: header_error,
+        }),
+        Box::new(ChainValidationError::NonConsecutiveHeight {
+            index: 1,
+            parent_height: 1u32.into(),
+            height: 3u32.into(),
+        }),
+        Box::new(ChainValidationError::PrevRootMismatch { index: 1 }),
+        Box::new(DaHeightError::Decreasing {
+            parent: 2u64.into(),
+            current: 1u64.into(),
+        }),
+        Box::new(DaHeightError::GapTooLarge {
+            parent: 1u64.into(),
+            current: 10u64.into(),
+            max_gap: 2,
+        }),
+        Box::new(TimeError::NonMonotonic {
+            parent: Tai64(2),
+            current: Tai64(1),
+        }),
+        Box::new(TimeError::ExcessiveDrift {
+            parent: Tai64(1),
+            current: Tai64(10),
+            max_drift_secs: 2,
+        }),
+        Box::new(TimeError::TooFarInFuture {
+            now: Tai64(1),
+            time: Tai64(10),
+            max_future_secs: 2,
+        }),
+        Box::new(GenerateError::DuplicateMessageId(MessageId::zeroed())),
+        Box::new(GenerateError::TooManyTransactions(1)),
+        Box::new(GenerateError::TooManyMessages(1)),
+        Box::new(CanonicalBytesError::InvalidLength(1)),
+        Box::new(CanonicalBytesError::UnknownVersion(2)),
+    ];
+
+    let messages: std::collections::HashSet<_> =
+        errors.iter().map(|error| error.to_string()).collect();
+    assert!(messages.iter().all(|message| !message.is_empty()));
+    assert_eq!(messages.len(), errors.len());
+
+    // The errors are available from both modules.
+    let _: error::TimeError = TimeError::NonMonotonic {
+        parent: Tai64(2),
+        current: Tai64(1),
+    };
+}
+
+#[test]
+fn hash_into_then_digest_equals_hash() {
+    let header = non_empty_header();
+
+    let mut hasher = crate::fuel_crypto::Hasher::default();
+    header.consensus.hash_into(&mut hasher);
+    assert_eq!(BlockId::from(hasher.digest()), header.consensus.hash());
+
+    let mut prefixed = crate::fuel_crypto::Hasher::default();
+    prefixed.input([0u8]);
+    header.consensus.hash_into(&mut prefixed);
+    assert_ne!(BlockId::from(prefixed.digest()), header.consensus.hash());
+}
+
+#[test]
+fn ensure_metadata_populates_only_missing_metadata() {
+    let mut header = non_empty_header().without_metadata();
+    header.ensure_metadata();
+    assert_eq!(
+        header.metadata(),
+        Some(&BlockHeaderMetadata::new(header.hash()))
+    );
+
+    // The existing metadata is kept as is, even if it is stale.
+    let stale = BlockHeaderMetadata::new(BlockId::from([1u8; 32]));
+    header.metadata = Some(stale.clone());
+    header.ensure_metadata();
+    assert_eq!(header.metadata(), Some(&stale));
+}
+
+#[cfg(all(feature = "serde", not(feature = "serialize-metadata")))]
+#[test]
+fn json_uses_camel_case_field_names() {
+    let header = non_empty_header();
+    let json = serde_json::to_value(&header).unwrap();
+
+    let object = |value: &serde_json::Value| -> Vec<String> {
+        let mut keys: Vec<_> = value.as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    };
+    assert_eq!(object(&json), ["application", "consensus"]);
+    let mut application_keys = vec!["daHeight", "generated"];
+    if cfg!(feature = "multi-da") {
+        application_keys.insert(0, "daChain");
+    }
+    assert_eq!(object(&json["application"]), application_keys);
+    assert_eq!(
+        object(&json["application"]["generated"]),
+        [
+            "messageReceiptCount",
+            "messageReceiptRoot",
+            "transactionsCount",
+            "transactionsRoot"
+        ]
+    );
+    let mut consensus_keys = vec!["generated", "height", "prevRoot", "time"];
+    if cfg!(feature = "nanos") {
+        consensus_keys.push("timeNanos");
+    }
+    assert_eq!(object(&json["consensus"]), consensus_keys);
+    assert_eq!(object(&json["consensus"]["generated"]), ["applicationHash"]);
+
+    let decoded: BlockHeader =
+        serde_json::from_str(&serde_json::to_string(&header).unwrap()).unwrap();
+    assert_eq!(decoded, header);
+}
+
+#[cfg(all(feature = "serde", not(feature = "serialize-metadata")))]
+#[test]
+fn binary_serialization_is_not_affected_by_field_names() {
+    let header = non_empty_header();
+    let bytes = postcard::to_allocvec(&header).unwrap();
+    let decoded: BlockHeader = postcard::from_bytes(&bytes).unwrap();
+
+    assert_eq!(decoded, header);
+    assert_eq!(
+        bytes,
+        postcard::to_allocvec(&(&header.application, &header.consensus)).unwrap()
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trips_v1_header() {
+    let mut v1 = non_empty_header();
+    v1.application.version = HeaderVersion::V1;
+    v1.recalculate_metadata();
+
+    let json = serde_json::to_value(&v1).unwrap();
+    assert_eq!(json["application"]["version"], "V1");
+    let decoded: BlockHeader = serde_json::from_str(&json.to_string()).unwrap();
+    assert_eq!(decoded.application.version, HeaderVersion::V1);
+    assert_eq!(decoded.id(), v1.id());
+    assert_eq!(decoded.validate(), Ok(()));
+
+    // The binary layout has no place for the version.
+    assert!(postcard::to_allocvec(&v1).is_err());
+}
+
+#[cfg(all(feature = "serde", not(feature = "serialize-metadata")))]
+#[test]
+fn serde_layout_omits_v0_version() {
+    let header = non_empty_header();
+
+    let json = serde_json::to_value(&header).unwrap();
+    assert!(json["application"].get("version").is_none());
+
+    let bytes = postcard::to_allocvec(&header).unwrap();
+    let decoded: BlockHeader = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.application.version, HeaderVersion::V0);
+    assert_eq!(decoded.id(), header.id());
+}
+
+#[cfg(all(feature = "serde", not(feature = "serialize-metadata")))]
+#[test]
+fn serde_skips_metadata_by_default() {
+    let header = non_empty_header();
+
+    let json = serde_json::to_value(&header).unwrap();
+    assert!(json.get("metadata").is_none());
+
+    let bytes = postcard::to_allocvec(&header).unwrap();
+    let decoded: BlockHeader = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.metadata(), None);
+    assert_eq!(decoded.id(), header.id());
+}
+
+#[cfg(feature = "serialize-metadata")]
+#[test]
+fn serde_persists_metadata_id() {
+    let header = non_empty_header();
+
+    let json = serde_json::to_value(&header).unwrap();
+    assert_eq!(json["metadata"], serde_json::to_value(header.id()).unwrap());
+
+    let bytes = postcard::to_allocvec(&header).unwrap();
+    let decoded: BlockHeader = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.metadata(), header.metadata());
+
+    // Headers without the cached id are persisted as well.
+    let header = header.without_metadata();
+    let bytes = postcard::to_allocvec(&header).unwrap();
+    let decoded: BlockHeader = postcard::from_bytes(&bytes).unwrap();
+    assert_eq!(decoded.metadata(), None);
+}
+
+#[cfg(feature = "serialize-metadata")]
+#[test]
+fn serde_rejects_stale_metadata_id() {
+    let mut header = non_empty_header();
+    header.metadata = Some(BlockHeaderMetadata::new(BlockId::from([1u8; 32])));
+
+    let json = serde_json::to_string(&header).unwrap();
+    let error = serde_json::from_str::<BlockHeader>(&json).unwrap_err();
+    assert!(error.to_string().contains("cached"), "{error}");
+
+    let bytes = postcard::to_allocvec(&header).unwrap();
+    assert!(postcard::from_bytes::<BlockHeader>(&bytes).is_err());
+}
+
+#[test]
+fn view_borrows_header_fields() {
+    let header = non_empty_header();
+    let view = header.view();
+
+    assert_eq!(view.version, header.application.version as u8);
+    assert_eq!(view.da_height, 1);
+    assert_eq!(view.transactions_count, 2);
+    assert_eq!(view.message_receipt_count, 3);
+    assert_eq!(view.height, 3);
+    assert_eq!(view.time, 4);
+    assert_eq!(view.prev_root, &[2u8; 32]);
+
+    let application = &header.application.generated;
+    let consensus = &header.consensus;
+    assert!(core::ptr::eq(
+        view.transactions_root,
+        &*application.transactions_root
+    ));
+    assert!(core::ptr::eq(
+        view.message_receipt_root,
+        &*application.message_receipt_root
+    ));
+    assert!(core::ptr::eq(view.prev_root, &*consensus.prev_root));
+    assert!(core::ptr::eq(
+        view.application_hash,
+        &*consensus.generated.application_hash
+    ));
+}
+
+#[test]
+fn partial_header_da_height_accessors() {
+    let mut partial = PartialBlockHeader::default();
+    assert_eq!(partial.da_height(), DaBlockHeight(0));
+
+    partial.set_da_height(42u64.into());
+    assert_eq!(partial.da_height(), DaBlockHeight(42));
+    assert_eq!(partial.generate(&[], &[]).da_height, DaBlockHeight(42));
+}
+
+#[test]
+fn compact_round_trip_for_empty_block() {
+    let header = PartialBlockHeaderBuilder::new()
+        .version(HeaderVersion::V1)
+        .da_height(1u64.into())
+        .prev_root([2u8; 32].into())
+        .height(3u32.into())
+        .time(Tai64(4))
+        .build()
+        .generate(&[], &[]);
+
+    let compact = header.try_to_compact().unwrap();
+    assert_eq!(compact.len(), COMPACT_HEADER_SIZE);
+
+    let decoded = BlockHeader::from_compact(&compact).unwrap();
+    assert_eq!(decoded, header);
+    assert_eq!(decoded.metadata(), header.metadata());
+}
+
+#[test]
+fn compact_is_not_available_for_non_empty_block() {
+    assert_eq!(non_empty_header().try_to_compact(), None);
+
+    let with_message = PartialBlockHeader::default().generate(&[], &message_ids(1));
+    assert_eq!(with_message.try_to_compact(), None);
+}
+
+#[test]
+fn from_compact_rejects_unknown_bytes() {
+    let header = PartialBlockHeader::default().generate(&[], &[]);
+    let compact = header.try_to_compact().unwrap();
+
+    let mut unknown_version = compact;
+    unknown_version[0] = 0xff;
+    assert_eq!(
+        BlockHeader::from_compact(&unknown_version),
+        Err(CanonicalBytesError::UnknownVersion(0xff))
+    );
+}
+
+#[test]
+fn is_sibling_of_detects_forks() {
+    let header = non_empty_header();
+    let mut sibling = header.clone();
+    sibling.consensus.time = Tai64(5);
+    sibling.recalculate_metadata();
+
+    assert!(header.is_sibling_of(&sibling));
+    assert!(!header.is_sibling_of(&header.clone()));
+
+    let headers = chain_of(2);
+    assert!(!headers[0].is_sibling_of(&headers[1]));
+}
+
+#[test]
+fn next_prev_root_is_prev_root_of_child() {
+    let headers = chain_of(3);
+    let mut accumulator = PrevRootBuilder::new();
+
+    for pair in headers.windows(2) {
+        let (parent, child) = (&pair[0], &pair[1]);
+        assert_eq!(child.prev_root(), &parent.next_prev_root(&accumulator));
+        accumulator.push(&parent.id());
+    }
+}
+
+#[test]
+fn links_to_accepts_direct_child() {
+    let headers = chain_of(4);
+    let mut accumulator = PrevRootBuilder::new();
+
+    for pair in headers.windows(2) {
+        let (parent, child) = (&pair[0], &pair[1]);
+        assert!(child.links_to(parent, &accumulator));
+        accumulator.push(&parent.id());
+    }
+}
+
+#[test]
+fn links_to_rejects_skipped_height() {
+    let headers = chain_of(2);
+    let parent = &headers[0];
+    let accumulator = PrevRootBuilder::new();
+
+    // The `prev_root` is correct, but the height skips one block.
+    let skipped = PartialBlockHeaderBuilder::new()
+        .prev_root(*headers[1].prev_root())
+        .height(2u32.into())
+        .build()
+        .generate(&[], &[]);
+    assert!(!skipped.links_to(parent, &accumulator));
+    assert!(headers[1].links_to(parent, &accumulator));
+}
+
+#[test]
+fn to_partial_keeps_source_fields() {
+    let header = non_empty_header();
+    let partial = header.to_partial();
+
+    assert_eq!(partial.da_height(), header.da_height);
+    assert_eq!(partial.prev_root(), header.prev_root());
+    assert_eq!(partial.height(), header.height());
+    assert_eq!(partial.time(), header.time());
+    assert_eq!(partial.consensus_type(), header.consensus_type());
+    assert_eq!(partial.generate(&transactions(2), &message_ids(3)), header);
+}
+
+#[test]
+fn signed_header_verifies_producer_signature() {
+    use crate::fuel_crypto::SecretKey;
+
+    let secret = SecretKey::try_from(&[1u8; 32][..]).unwrap();
+    let other = SecretKey::try_from(&[2u8; 32][..]).unwrap();
+    let header = non_empty_header();
+    let signature = Signature::sign(&secret, &header.id().into_message());
+
+    let signed = SignedBlockHeader::new(header, signature);
+    assert!(signed.verify_signature(&secret.public_key()));
+    assert!(!signed.verify_signature(&other.public_key()));
+}
+
+#[test]
+fn chain_of_builds_valid_chain() {
+    assert!(chain_of(0).is_empty());
+
+    let headers = chain_of(4);
+    assert_eq!(headers.len(), 4);
+    for (height, header) in headers.iter().enumerate() {
+        assert_eq!(header.height_u32(), height as u32);
+        assert_eq!(header.transactions_count, 0);
+        assert_eq!(header.message_receipt_count, 0);
+    }
+    assert!(headers
+        .windows(2)
+        .all(|pair| pair[0].time() < pair[1].time()));
+    assert_eq!(headers[0].validate_genesis(), Ok(()));
+    assert_eq!(
+        validate_chain(&headers, &mut PrevRootBuilder::new()),
+        Ok(())
+    );
+}
+
+#[test]
+fn validate_chain_accepts_linked_headers() {
+    let headers = chain_of(5);
+    let mut accumulator = PrevRootBuilder::new();
+    assert_eq!(validate_chain(&headers, &mut accumulator), Ok(()));
+
+    // The accumulator can be reused for the next range.
+    let mut expected = PrevRootBuilder::new();
+    for header in &headers {
+        expected.push(&header.id());
+    }
+    assert_eq!(accumulator.root(), expected.root());
+
+    let headers = chain_of(8);
+    let mut accumulator = PrevRootBuilder::new();
+    assert_eq!(validate_chain(&headers[..3], &mut accumulator), Ok(()));
+    assert_eq!(validate_chain(&headers[3..], &mut accumulator), Ok(()));
+}
+
+#[test]
+fn validate_chain_reports_first_invalid_header() {
+    let headers = chain_of(5);
+
+    for broken in 1..headers.len() {
+        // Break the linkage by replacing the header with an unrelated one.
+        let mut unlinked = headers.clone();
+        unlinked[broken] = PartialBlockHeaderBuilder::new()
+            .height((broken as u32).into())
+            .build()
+            .generate(&[], &[]);
+        let error = validate_chain(&unlinked, &mut PrevRootBuilder::new()).unwrap_err();
+        assert_eq!(
+            error,
+            ChainValidationError::PrevRootMismatch { index: broken }
+        );
+
+        // Break the height continuity.
+        let mut skipped = headers.clone();
+        skipped.remove(broken);
+        if broken < skipped.len() {
+            let error =
+                validate_chain(&skipped, &mut PrevRootBuilder::new()).unwrap_err();
+            assert_eq!(
+                error,
+                ChainValidationError::NonConsecutiveHeight {
+                    index: broken,
+                    parent_height: (broken as u32 - 1).into(),
+                    height: (broken as u32 + 1).into(),
+                }
+            );
+        }
+    }
+
+    // Break the linkage of the first header.
+    let mut unlinked = headers.clone();
+    unlinked[0].consensus.prev_root = [1u8; 32].into();
+    unlinked[0].recalculate_metadata();
+    let error = validate_chain(&unlinked, &mut PrevRootBuilder::new()).unwrap_err();
+    assert_eq!(error, ChainValidationError::PrevRootMismatch { index: 0 });
+
+    // The first header of the range must link to the blocks in the accumulator.
+    let mut accumulator = PrevRootBuilder::new();
+    assert_eq!(validate_chain(&headers[..2], &mut accumulator), Ok(()));
+    let error = validate_chain(&headers[3..], &mut accumulator).unwrap_err();
+    assert_eq!(error, ChainValidationError::PrevRootMismatch { index: 0 });
+    let error = validate_chain(&headers[2..], &mut PrevRootBuilder::new()).unwrap_err();
+    assert_eq!(error, ChainValidationError::PrevRootMismatch { index: 0 });
+
+    // The failed validation doesn't advance the accumulator.
+    assert_eq!(accumulator.count(), 2);
+    let mut accumulator = PrevRootBuilder::new();
+    let mut broken_tail = headers.clone();
+    broken_tail[3].consensus.prev_root = [1u8; 32].into();
+    broken_tail[3].recalculate_metadata();
+    let error = validate_chain(&broken_tail, &mut accumulator).unwrap_err();
+    assert_eq!(error, ChainValidationError::PrevRootMismatch { index: 3 });
+    assert_eq!(accumulator.count(), 0);
+
+    // Break the internal consistency.
+    for broken in 0..headers.len() {
+        let mut inconsistent = headers.clone();
+        inconsistent[broken].consensus.generated.application_hash = Bytes32::zeroed();
+        let error =
+            validate_chain(&inconsistent, &mut PrevRootBuilder::new()).unwrap_err();
+        assert!(matches!(
+            error,
+            ChainValidationError::InvalidHeader { index, .. } if index == broken
+        ));
+        assert_eq!(error.index(), broken);
+    }
+}
+
+#[test]
+fn verify_roots_accepts_matching_sets() {
+    let transactions = transactions(2);
+    let message_ids = message_ids(3);
+    let header = non_empty_header();
+    let tx_bytes: Vec<Vec<u8>> = transactions
+        .iter()
+        .map(|tx| tx.clone().to_bytes())
+        .collect();
+
+    assert!(header.verify_transactions_root(&tx_bytes));
+    assert!(header.verify_message_receipt_root(&message_ids));
+}
+
+#[test]
+fn verify_roots_rejects_mismatching_sets() {
+    let header = non_empty_header();
+    let tx_bytes: Vec<Vec<u8>> = transactions(3)
+        .iter()
+        .map(|tx| tx.clone().to_bytes())
+        .collect();
+
+    assert!(!header.verify_transactions_root(&tx_bytes));
+    assert!(!header.verify_transactions_root(&tx_bytes[1..]));
+    assert!(!header.verify_message_receipt_root(&message_ids(2)));
+    assert!(!header.verify_message_receipt_root(&message_ids(4)[1..]));
+}
+
+#[test]
+fn verify_roots_rejects_count_mismatch_with_same_root() {
+    let tx_bytes: Vec<Vec<u8>> = transactions(2)
+        .iter()
+        .map(|tx| tx.clone().to_bytes())
+        .collect();
+    let message_ids = message_ids(3);
+    let header = non_empty_header();
+
+    let wrong_counts = header.to_partial().generate_with_roots(
+        header.transactions_count + 1,
+        header.message_receipt_count + 1,
+        header.transactions_root,
+        header.message_receipt_root,
+    );
+    assert!(!wrong_counts.verify_transactions_root(&tx_bytes));
+    assert!(!wrong_counts.verify_message_receipt_root(&message_ids));
+}
+
+#[test]
+fn height_round_trips_through_u32() {
+    for height in [0u32, 1, 3, u32::MAX] {
+        let header = PartialBlockHeaderBuilder::new()
+            .height_u32(height)
+            .build()
+            .generate(&[], &[]);
+
+        assert_eq!(header.height(), &BlockHeight::from(height));
+        assert_eq!(header.height_u32(), height);
+        assert_eq!(u32::from(header), height);
+    }
+}
+
+#[test]
+fn da_height_u64_matches_constructed_value() {
+    for da_height in [0u64, 1, u64::MAX] {
+        let header = PartialBlockHeaderBuilder::new()
+            .da_height(da_height.into())
+            .build()
+            .generate(&[], &[]);
+
+        assert_eq!(header.da_height_u64(), da_height);
+    }
+}
+
+#[cfg(feature = "protobuf")]
+#[test]
+fn protobuf_round_trip() {
+    use prost::Message;
+
+    for header in [
+        PartialBlockHeader::default().generate(&[], &[]),
+        non_empty_header(),
+    ] {
+        let proto = HeaderProto::from(&header);
+        let decoded = HeaderProto::decode(proto.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(decoded, proto);
+        assert_eq!(BlockHeader::try_from(decoded), Ok(header));
+    }
+}
+
+#[cfg(feature = "protobuf")]
+#[test]
+fn protobuf_rejects_invalid_headers() {
+    let header = non_empty_header();
+
+    let mut short_root = HeaderProto::from(&header);
+    short_root.transactions_root.pop();
+    assert_eq!(
+        BlockHeader::try_from(short_root),
+        Err(ProtoHeaderError::InvalidLength {
+            field: "transactions_root",
+            len: 31,
+        })
+    );
+
+    let mut wrong_hash = HeaderProto::from(&header);
+    wrong_hash.application_hash = vec![1u8; 32];
+    assert!(matches!(
+        BlockHeader::try_from(wrong_hash),
+        Err(ProtoHeaderError::InvalidHeader(
+            HeaderValidationError::ApplicationHashMismatch { .. }
+        ))
+    ));
+
+    let mut wrong_version = HeaderProto::from(&header);
+    wrong_version.version = 256;
+    assert_eq!(
+        BlockHeader::try_from(wrong_version),
+        Err(ProtoHeaderError::UnsupportedValue {
+            field: "version",
+            value: 256,
+        })
+    );
+}
+
+#[test]
+fn id_meets_difficulty_counts_leading_zero_bits() {
+    let leading_zeros = |header: &BlockHeader| {
+        let id = header.id();
+        let first = u16::from_be_bytes([id.as_slice()[0], id.as_slice()[1]]);
+        first.leading_zeros()
+    };
+    let find = |matches: &dyn Fn(u32) -> bool| {
+        (0..)
+            .map(|time| BlockHeader::new_block(1u32.into(), Tai64(time)))
+            .find(|header| matches(leading_zeros(header)))
+            .unwrap()
+    };
+
+    let four = find(&|zeros| (4..8).contains(&zeros));
+    assert!(four.id_meets_difficulty(0));
+    assert!(four.id_meets_difficulty(4));
+    assert!(!four.id_meets_difficulty(8));
+
+    let eight = find(&|zeros| (8..16).contains(&zeros));
+    assert!(eight.id_meets_difficulty(4));
+    assert!(eight.id_meets_difficulty(8));
+    assert!(!eight.id_meets_difficulty(16));
+
+    let none = find(&|zeros| zeros == 0);
+    assert!(none.id_meets_difficulty(0));
+    assert!(!none.id_meets_difficulty(1));
+    assert!(!none.id_meets_difficulty(4));
+    assert!(!none.id_meets_difficulty(257));
+}
+
+#[test]
+fn accumulate_ids_commits_to_every_header() {
+    let headers = chain_of(4);
+    let root = accumulate_ids(&headers);
+    assert_eq!(root, accumulate_ids(&headers.clone()));
+    assert_ne!(root, accumulate_ids(&headers[..3]));
+    assert_eq!(accumulate_ids(&[]), EMPTY_MERKLE_ROOT);
+
+    let mut accumulator = PrevRootBuilder::new();
+    headers
+        .iter()
+        .for_each(|header| accumulator.push(&header.id()));
+    assert_eq!(root, accumulator.root());
+
+    for index in 0..headers.len() {
+        let mut changed = headers.clone();
+        changed[index].application.da_height = 123u64.into();
+        changed[index].recalculate_metadata();
+        assert_ne!(root, accumulate_ids(&changed), "header {index}");
+    }
+}
+
+#[test]
+fn genesis_partial_header_generates_valid_genesis() {
+    let time = Tai64::from_unix(1_700_000_000);
+    let partial = PartialBlockHeader::genesis(DaBlockHeight::default(), time);
+    assert_eq!(partial.height(), &BlockHeight::default());
+    assert_eq!(partial.prev_root(), &Bytes32::zeroed());
+    assert_eq!(partial.time(), time);
+
+    let header = partial.generate(&transactions(2), &message_ids(1));
+    assert!(header.is_genesis());
+    assert_eq!(header.validate_genesis(), Ok(()));
+
+    let header = PartialBlockHeader::genesis(5u64.into(), time).generate(&[], &[]);
+    assert_eq!(
+        header.validate_genesis(),
+        Err(HeaderValidationError::GenesisConstraintViolated {
+            da_height: 5u64.into(),
+            prev_root: Bytes32::zeroed(),
+        })
+    );
+}
+
+#[test]
+fn arc_headers_share_roots_and_keep_hashes() {
+    let mut headers = chain_of(10);
+    headers.push(non_empty_header());
+    let mut pool = RootPool::new();
+    let interned: Vec<_> = headers
+        .iter()
+        .map(|header| ArcBlockHeader::new(header, &mut pool))
+        .collect();
+
+    for (header, interned) in headers.iter().zip(&interned) {
+        assert_eq!(interned.id(), header.id());
+        assert_eq!(interned.height(), header.height());
+        let restored = interned.to_header();
+        assert_eq!(&restored, header);
+        assert_eq!(restored.hash(), header.id());
+        assert_eq!(restored.validate(), Ok(()));
+    }
+    // The root of the empty tree and one application hash are shared
+    // by the empty blocks.
+    assert_eq!(pool.len(), 2 + 3);
+}
+
+#[test]
+fn arc_header_recalculates_stale_id() {
+    let header = non_empty_header();
+    let mut stale = header.clone();
+    stale.metadata = Some(BlockHeaderMetadata::new(BlockId::from([1u8; 32])));
+
+    let interned = ArcBlockHeader::new(&stale, &mut RootPool::new());
+    assert_eq!(interned.id(), header.id());
+    assert_eq!(interned.to_header().metadata(), header.metadata());
+}
+
+#[cfg(all(
+    target_pointer_width = "64",
+    not(any(feature = "multi-da", feature = "nanos"))
+))]
+#[test]
+fn arc_header_size_matches_the_documentation() {
+    assert_eq!(core::mem::size_of::<ArcBlockHeader>(), 136);
+    assert_eq!(core::mem::size_of::<BlockHeader>(), 216);
+}
+
+#[test]
+fn into_validated_accepts_only_valid_headers() {
+    let header = non_empty_header();
+    let validated = header.clone().into_validated().unwrap();
+    assert_eq!(validated.id(), header.id());
+    assert_eq!(validated.into_inner(), header);
+
+    let mut invalid = non_empty_header();
+    invalid.consensus.generated.application_hash = Bytes32::zeroed();
+    assert_eq!(
+        invalid.into_validated(),
+        Err(HeaderValidationError::ApplicationHashMismatch {
+            expected: *header.application_hash(),
+            actual: Bytes32::zeroed(),
+        })
+    );
+}
+
+#[test]
+fn hashing_layout_matches_hashed_bytes() {
+    let header = non_empty_header();
+    let field = |name: &str| -> Vec<u8> {
+        match name {
+            "da_height" => header.da_height.0.to_be_bytes().to_vec(),
+            #[cfg(feature = "multi-da")]
+            "da_chain" => header.da_chain.0.to_be_bytes().to_vec(),
+            "transactions_count" => header.transactions_count.to_be_bytes().to_vec(),
+            "message_receipt_count" => {
+                header.message_receipt_count.to_be_bytes().to_vec()
+            }
+            "transactions_root" => header.transactions_root.to_vec(),
+            "message_receipt_root" => header.message_receipt_root.to_vec(),
+            "prev_root" => header.prev_root().to_vec(),
+            "height" => header.height().to_bytes().to_vec(),
+            "time" => header.time().0.to_be_bytes().to_vec(),
+            #[cfg(feature = "nanos")]
+            "time_nanos" => header.consensus.time_nanos.to_be_bytes().to_vec(),
+            "application_hash" => header.application_hash().to_vec(),
+            _ => panic!("Unknown field {name}"),
+        }
+    };
+    let preimage = |layout: &[HashedField]| {
+        layout
+            .iter()
+            .flat_map(|hashed| {
+                let bytes = field(hashed.name);
+                assert_eq!(bytes.len(), hashed.len, "{}", hashed.name);
+                bytes
+            })
+            .collect::<Vec<u8>>()
+    };
+    let layout = hashing_layout();
+
+    assert_eq!(
+        crate::fuel_crypto::Hasher::hash(preimage(layout.application)),
+        header.application.hash()
+    );
+    assert_eq!(
+        BlockId::from(crate::fuel_crypto::Hasher::hash(preimage(layout.consensus))),
+        header.id()
+    );
+}
+
+#[cfg(feature = "graphql")]
+fn graphql_header(header: &BlockHeader) -> GraphQlBlockHeader {
+    GraphQlBlockHeader {
+        id: format!("{:#x}", header.id()),
+        da_height: header.da_height.0.to_string(),
+        transactions_count: header.transactions_count.to_string(),
+        message_receipt_count: header.message_receipt_count.to_string(),
+        transactions_root: format!("{:#x}", header.transactions_root),
+        message_receipt_root: format!("{:#x}", header.message_receipt_root),
+        height: header.height_u32().to_string(),
+        prev_root: format!("{:#x}", header.prev_root()),
+        time: header.time().0.to_string(),
+        application_hash: format!("{:#x}", header.application_hash()),
+    }
+}
+
+#[cfg(feature = "graphql")]
+#[test]
+fn graphql_header_converts_into_block_header() {
+    let header = non_empty_header();
+
+    let converted = BlockHeader::try_from(graphql_header(&header)).unwrap();
+
+    assert_eq!(converted, header);
+    assert_eq!(converted.id(), header.id());
+}
+
+#[cfg(feature = "graphql")]
+#[test]
+fn graphql_header_rejects_malformed_and_tampered_fields() {
+    let header = non_empty_header();
+
+    let malformed = GraphQlBlockHeader {
+        transactions_root: "0xnot_hex".to_string(),
+        ..graphql_header(&header)
+    };
+    assert_eq!(
+        BlockHeader::try_from(malformed),
+        Err(GraphQlHeaderError::MalformedField {
+            field: "transactionsRoot",
+            value: "0xnot_hex".to_string(),
+        })
+    );
+
+    let tampered = GraphQlBlockHeader {
+        transactions_count: "100".to_string(),
+        ..graphql_header(&header)
+    };
+    assert!(matches!(
+        BlockHeader::try_from(tampered),
+        Err(GraphQlHeaderError::InvalidHeader(
+            HeaderValidationError::ApplicationHashMismatch { .. }
+        ))
+    ));
+}
+
+#[test]
+fn advance_time_adds_intervals_and_saturates() {
+    let block_time = 10;
+    let mut time = Tai64::UNIX_EPOCH;
+    for _ in 0..3 {
+        time = advance_time(time, block_time);
+    }
+    assert_eq!(time, Tai64::from_unix(30));
+
+    let max = Tai64((1 << 63) - 1);
+    assert_eq!(advance_time(Tai64(max.0 - 5), block_time), max);
+    assert_eq!(advance_time(max, u64::MAX), max);
+}
+
+#[test]
+fn hash_preimage_digest_matches_hash() {
+    let header = non_empty_header();
+
+    let preimage = header.application.hash_preimage();
+    let layout = hashing_layout();
+    let len =
+        |fields: &[HashedField]| fields.iter().map(|field| field.len).sum::<usize>();
+    assert_eq!(preimage.len(), len(layout.application));
+    assert_eq!(
+        crate::fuel_crypto::Hasher::hash(&preimage),
+        header.application.hash()
+    );
+
+    let preimage = header.consensus.hash_preimage();
+    assert_eq!(preimage.len(), len(layout.consensus));
+    assert_eq!(
+        BlockId::from(crate::fuel_crypto::Hasher::hash(&preimage)),
+        header.consensus.hash()
+    );
+
+    // The version is a part of the preimage since `V1`.
+    let mut application = header.application.clone();
+    application.version = HeaderVersion::V1;
+    let preimage = application.hash_preimage();
+    assert_eq!(preimage[0], HeaderVersion::V1 as u8);
+    assert_eq!(
+        crate::fuel_crypto::Hasher::hash(&preimage),
+        application.hash()
+    );
+}
+
+#[test]
+fn filter_by_height_range_keeps_inclusive_bounds_and_order() {
+    let mut headers = chain_of(6);
+    headers.reverse();
+    let heights = |start: u32, end: u32| -> Vec<u32> {
+        let range = BlockHeight::from(start)..=BlockHeight::from(end);
+        filter_by_height_range(&headers, range)
+            .into_iter()
+            .map(BlockHeader::height_u32)
+            .collect()
+    };
+
+    assert_eq!(heights(1, 3), vec![3, 2, 1]);
+    assert_eq!(heights(4, 10), vec![5, 4]);
+    assert_eq!(heights(2, 2), vec![2]);
+    assert_eq!(heights(4, 2), Vec::<u32>::new());
+    assert_eq!(heights(7, 9), Vec::<u32>::new());
+}
+
+#[test]
+fn index_by_height_indexes_chain_in_height_order() {
+    let headers = chain_of(5);
+    let mut shuffled = headers.clone();
+    shuffled.swap(0, 3);
+
+    let index = index_by_height(shuffled);
+
+    assert_eq!(index.len(), 5);
+    assert!(index.values().eq(headers.iter()));
+    let range: Vec<_> = index
+        .range(BlockHeight::from(1u32)..=BlockHeight::from(2u32))
+        .map(|(height, _)| **height)
+        .collect();
+    assert_eq!(range, vec![1, 2]);
+}
+
+#[test]
+fn index_by_height_keeps_last_header_of_duplicate_height() {
+    let headers = chain_of(3);
+    let sibling = BlockHeader::new_block(1u32.into(), Tai64(100));
+    assert!(sibling.is_sibling_of(&headers[1]));
+
+    let mut with_sibling = headers.clone();
+    with_sibling.push(sibling.clone());
+    let index = index_by_height(with_sibling);
+
+    assert_eq!(index.len(), 3);
+    assert_eq!(index[&BlockHeight::from(1u32)], sibling);
+    assert_eq!(index[&BlockHeight::from(2u32)], headers[2]);
+}
+
+#[test]
+fn with_next_height_builds_valid_chain() {
+    let mut accumulator = PrevRootBuilder::new();
+    let mut headers = vec![PartialBlockHeader::default().generate(&[], &[])];
+    for _ in 1..3 {
+        let parent = headers.last().unwrap();
+        let child = parent.with_next_height(&accumulator).generate(&[], &[]);
+        accumulator.push(&parent.id());
+        headers.push(child);
+    }
+
+    assert_eq!(headers, chain_of(3));
+    assert_eq!(
+        validate_chain(&headers, &mut PrevRootBuilder::new()),
+        Ok(())
+    );
+}
+
+#[test]
+fn prev_root_builder_matches_merkle_tree() {
+    let mut builder = PrevRootBuilder::new();
+    let mut tree = fuel_merkle::binary::in_memory::MerkleTree::new();
+
+    for i in 0..33u8 {
+        assert_eq!(builder.root(), Bytes32::from(tree.root()));
+        assert_eq!(builder.count(), i as u64);
+        let id = BlockId::from([i; 32]);
+        builder.push(&id);
+        tree.push(id.as_slice());
+    }
+}
+
+#[test]
+fn prev_root_builder_resumes_from_checkpoint() {
+    let headers = chain_of(12);
+
+    let mut from_genesis = PrevRootBuilder::new();
+    for header in &headers[..7] {
+        from_genesis.push(&header.id());
+    }
+    let checkpoint_root = headers[7].prev_root();
+    let mut resumed = PrevRootBuilder::from_checkpoint(
+        *checkpoint_root,
+        7,
+        from_genesis.peaks().to_vec(),
+    )
+    .expect("The checkpoint is valid");
+
+    for header in &headers[7..] {
+        assert_eq!(
+            resumed.root_with(&header.id()),
+            header.next_prev_root(&from_genesis)
+        );
+        resumed.push(&header.id());
+        from_genesis.push(&header.id());
+    }
+    assert_eq!(resumed.root(), from_genesis.root());
+
+    // The checkpoint with the wrong root or number of peaks is rejected.
+    let peaks = PrevRootBuilder::new().peaks().to_vec();
+    assert!(PrevRootBuilder::from_checkpoint(*checkpoint_root, 7, peaks).is_none());
+    let mut peaks = from_genesis.peaks().to_vec();
+    peaks.reverse();
+    assert!(PrevRootBuilder::from_checkpoint(from_genesis.root(), 12, peaks).is_none());
+}
+
+#[cfg(feature = "multi-da")]
+#[test]
+fn da_chain_is_part_of_hash_and_encodings() {
+    use crate::blockchain::primitives::DaChainId;
+
+    let header_on = |da_chain: u64| {
+        PartialBlockHeaderBuilder::new()
+            .da_height(1u64.into())
+            .da_chain(DaChainId(da_chain))
+            .build()
+            .generate(&[], &[])
+    };
+    let (default, other) = (header_on(0), header_on(7));
+
+    assert_ne!(default.application_hash(), other.application_hash());
+    assert_ne!(default.id(), other.id());
+    // The chain goes after the `da_height` in the preimage.
+    assert_eq!(other.application.hash_preimage()[8..16], 7u64.to_be_bytes());
+
+    let decoded = BlockHeader::from_canonical_bytes(&other.to_canonical_bytes()).unwrap();
+    assert_eq!(decoded.da_chain, DaChainId(7));
+    assert_eq!(decoded.id(), other.id());
+    let compact = other.try_to_compact().unwrap();
+    assert_eq!(
+        BlockHeader::from_compact(&compact).unwrap().id(),
+        other.id()
+    );
+}
@@ -99,6 +99,14 @@ impl AsRef<[u8]> for BlockId {
 #[rem(forward)]
 pub struct DaBlockHeight(pub u64);
 
+/// The identifier of the data availability layer chain
+#[cfg(feature = "multi-da")]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(
+    Copy, Clone, Debug, Default, PartialEq, PartialOrd, Eq, Ord, Display, Into, From, Hash,
+)]
+pub struct DaChainId(pub u64);
+
 impl From<DaBlockHeight> for Vec<u8> {
     fn from(height: DaBlockHeight) -> Self {
         height.0.to_be_bytes().to_vec()
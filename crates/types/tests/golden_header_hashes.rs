@@ -0,0 +1,231 @@
+//! The regression tests of the header hashes against the fixed vectors.
+//!
+//! The hashes are consensus-critical: if any of these tests fails, the change
+//! of `ApplicationHeader::hash` or `ConsensusHeader::hash` breaks the ids
+//! of the existing blocks.
+//!
+//! The `multi-da` feature adds the `da_chain` to the application hash and the
+//! `nanos` feature adds the `time_nanos` to the block id, so every test pins
+//! the vectors of each feature set.
+
+use fuel_core_types::{
+    blockchain::header::{
+        BlockHeader,
+        PartialBlockHeader,
+        PartialBlockHeaderBuilder,
+    },
+    fuel_crypto::Hasher,
+    fuel_types::Bytes32,
+    tai64::Tai64,
+};
+use std::str::FromStr;
+
+fn partial(
+    da_height: u64,
+    prev_root: Bytes32,
+    height: u32,
+    time: Tai64,
+) -> PartialBlockHeader {
+    PartialBlockHeaderBuilder::new()
+        .da_height(da_height.into())
+        .prev_root(prev_root)
+        .height(height.into())
+        .time(time)
+        .build()
+}
+
+/// The application hash vectors without and with the `multi-da` feature.
+struct ApplicationHash {
+    default: &'static str,
+    multi_da: &'static str,
+}
+
+impl ApplicationHash {
+    fn expected(&self) -> Bytes32 {
+        let hash = if cfg!(feature = "multi-da") {
+            self.multi_da
+        } else {
+            self.default
+        };
+        Bytes32::from_str(hash).unwrap()
+    }
+}
+
+/// The block id vectors for each combination of the `multi-da` and `nanos` features.
+struct Id {
+    default: &'static str,
+    multi_da: &'static str,
+    nanos: &'static str,
+    multi_da_nanos: &'static str,
+}
+
+impl Id {
+    fn expected(&self) -> Bytes32 {
+        let id = match (cfg!(feature = "multi-da"), cfg!(feature = "nanos")) {
+            (false, false) => self.default,
+            (true, false) => self.multi_da,
+            (false, true) => self.nanos,
+            (true, true) => self.multi_da_nanos,
+        };
+        Bytes32::from_str(id).unwrap()
+    }
+}
+
+fn assert_golden(header: &BlockHeader, application_hash: ApplicationHash, id: Id) {
+    let application_hash = application_hash.expected();
+    let id = id.expected();
+
+    assert_eq!(
+        header.application.hash(),
+        application_hash,
+        "application hash"
+    );
+    assert_eq!(header.application_hash(), &application_hash);
+    assert_eq!(Bytes32::from(header.consensus.hash()), id, "block id");
+    assert_eq!(Bytes32::from(header.id()), id);
+}
+
+#[test]
+fn genesis_header_hashes() {
+    let header = partial(0, Bytes32::zeroed(), 0, Tai64::UNIX_EPOCH).generate(&[], &[]);
+
+    assert_eq!(header.validate_genesis(), Ok(()));
+    assert_golden(
+        &header,
+        ApplicationHash {
+            default: "0xd550bfbb5a2be7cefdcbffb321f65db28f195c4a9379f8254805a17ff873f609",
+            multi_da:
+                "0xd217f979960dde2e747989e11d958a8ba444dad59bbd7b4bae289d5796cf3649",
+        },
+        Id {
+            default: "0xb1807ca9f2eec7e459b866ecf69b68679fc6b205a9a85c16bd4943d1bfc6fb2a",
+            multi_da:
+                "0x99c73a80eba542e0f6fa091ccec8b169aae175015019560ab020942725cf7bfb",
+            nanos: "0x1bb277e70272fcd3c6fb644196814f39398e08d72e6dbade6c5ddc6970efbc2a",
+            multi_da_nanos:
+                "0xc068f6b5cc552cd17d8a68a0b051d868aeeb3a48b7d3019b2a50f488e84225de",
+        },
+    );
+}
+
+#[test]
+fn empty_header_hashes() {
+    let header = partial(
+        5,
+        Bytes32::from([1u8; 32]),
+        10,
+        Tai64::from_unix(1_700_000_000),
+    )
+    .generate(&[], &[]);
+
+    assert_golden(
+        &header,
+        ApplicationHash {
+            default: "0x2e6e8146924bc205345d6630a812ea4c209d75c8e7055f84d68dfd975ddbd892",
+            multi_da:
+                "0xd57ec22e34e7a58405d9ed5d87b370fc84ab51222ae0ad535501b1ce3508f8e8",
+        },
+        Id {
+            default: "0x6f93f0506e598ee000556d4e968874cfe1b110d814075cb8a960209477388629",
+            multi_da:
+                "0x3b65b2a59b0767b12b6d26639a7755b45431263cf80e98d6ec4d0aef91193a3c",
+            nanos: "0x6d3d41f33a2d5db4d7b8c2a873836189d77d39a5b2e83eb1962fe6b6b7e312f7",
+            multi_da_nanos:
+                "0x98f7e10040b492f1c09a9ec063c7d50f572708b8e9be6176bc5bda6a91e15df2",
+        },
+    );
+}
+
+#[test]
+fn non_empty_header_hashes() {
+    let header = non_empty_header();
+
+    assert_golden(
+        &header,
+        ApplicationHash {
+            default: "0x692caaff0860ed512396d21d29333b261c0ede7dfc6017c9101668fd159099f8",
+            multi_da:
+                "0xc781fd01bebede3f7f6910c067a46a3f8b1145f9de190fabfd51996d7de40c07",
+        },
+        Id {
+            default: "0xd2951cfbc7cabe1f098fa5fa74d6244bdc46337a439adc89b01b22005869e6b4",
+            multi_da:
+                "0xb04cbabf26cd2e4b20cf7fefef7619ca4ebe3a01d93b42f696a756ac8ff14780",
+            nanos: "0x733d53f65881d701829169f01a5c419238543a7d5d4f063d07abb36d73ef6012",
+            multi_da_nanos:
+                "0x60afc9f684f24cbb412db5d3f62ec6a826ce10ba4d19415ea6bef7f4257a77a4",
+        },
+    );
+}
+
+#[test]
+fn default_header_hasher_matches_golden_hashes() {
+    let header = non_empty_header();
+
+    assert_eq!(
+        header.application.hash_with::<Hasher>(),
+        ApplicationHash {
+            default: "0x692caaff0860ed512396d21d29333b261c0ede7dfc6017c9101668fd159099f8",
+            multi_da:
+                "0xc781fd01bebede3f7f6910c067a46a3f8b1145f9de190fabfd51996d7de40c07",
+        }
+        .expected()
+    );
+    assert_eq!(
+        Bytes32::from(header.consensus.hash_with::<Hasher>()),
+        Id {
+            default: "0xd2951cfbc7cabe1f098fa5fa74d6244bdc46337a439adc89b01b22005869e6b4",
+            multi_da:
+                "0xb04cbabf26cd2e4b20cf7fefef7619ca4ebe3a01d93b42f696a756ac8ff14780",
+            nanos: "0x733d53f65881d701829169f01a5c419238543a7d5d4f063d07abb36d73ef6012",
+            multi_da_nanos:
+                "0x60afc9f684f24cbb412db5d3f62ec6a826ce10ba4d19415ea6bef7f4257a77a4",
+        }
+        .expected()
+    );
+}
+
+#[cfg(feature = "keccak-header")]
+#[test]
+fn keccak_header_hashes() {
+    use fuel_core_types::blockchain::header::KeccakHeaderHasher;
+
+    let header = non_empty_header();
+
+    assert_eq!(
+        header.application.hash_with::<KeccakHeaderHasher>(),
+        ApplicationHash {
+            default: "0xa1d6a60208d7beeb76ea4ee685f203d068e1f2a0c161286a46c7c7ee8537ae16",
+            multi_da:
+                "0xe797e6b7954ebaf09412cf8ee3c3b8c0c25e80607bda6c165a4aa2691100b87f",
+        }
+        .expected()
+    );
+    assert_eq!(
+        Bytes32::from(header.consensus.hash_with::<KeccakHeaderHasher>()),
+        Id {
+            default: "0xf51500d4dce8671d474ff24b281d06a504583ae550794e0ddaade5a1e63d7669",
+            multi_da:
+                "0x744565abed3df56955486a60aee5c80d1a58faf65fdc9563af082ba1705b8043",
+            nanos: "0xbf2c71f2ccd54080a5507ae1ff45fc6562f97b4befafbfe992da0e9f47ebf19c",
+            multi_da_nanos:
+                "0x0bcf2a46b72e6d54abf6f037fd67d8b8244ebf799991bcb25efb4cf871c23945",
+        }
+        .expected()
+    );
+}
+
+fn non_empty_header() -> BlockHeader {
+    partial(
+        7,
+        Bytes32::from([0x11u8; 32]),
+        42,
+        Tai64::from_unix(1_700_000_600),
+    )
+    .generate_with_roots(
+        3,
+        2,
+        Bytes32::from([0xaau8; 32]),
+        Bytes32::from([0xbbu8; 32]),
+    )
+}